@@ -0,0 +1,433 @@
+use crate::{MacAddress, MacParseError, MacPattern, MacPrefix};
+use alloc::vec::Vec;
+
+/// Whether an ACL rule allows or denies the addresses it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AclAction {
+    /// Matching addresses are allowed.
+    Allow,
+    /// Matching addresses are denied.
+    Deny,
+}
+
+/// The set of addresses an ACL rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AclMatcher {
+    /// Every address, written `any`.
+    Any,
+    /// A single address, e.g. `00:11:22:33:44:55`.
+    Address(MacAddress),
+    /// A block of addresses, e.g. `00:11:22:00:00:00/24`.
+    Prefix(MacPrefix),
+    /// A wildcard or value/mask pattern, e.g. `00:11:22:*:*:*` or
+    /// `00:11:22:00:00:00 FF:FF:FF:00:00:00`.
+    Pattern(MacPattern),
+    /// Group addresses, written `multicast`. This includes broadcast.
+    Multicast,
+    /// Individual addresses, written `unicast`.
+    Unicast,
+    /// Locally administered addresses, written `local`.
+    Local,
+    /// Universally administered addresses, written `universal`.
+    Universal,
+    /// The broadcast address, written `broadcast`.
+    Broadcast,
+}
+
+impl AclMatcher {
+    /// Returns `true` if `mac` is matched.
+    pub fn matches(&self, mac: &MacAddress) -> bool {
+        match self {
+            AclMatcher::Any => true,
+            AclMatcher::Address(addr) => addr == mac,
+            AclMatcher::Prefix(prefix) => prefix.contains(mac),
+            AclMatcher::Pattern(pattern) => pattern.matches(mac),
+            AclMatcher::Multicast => mac.is_multicast(),
+            AclMatcher::Unicast => mac.is_unicast(),
+            AclMatcher::Local => mac.is_local(),
+            AclMatcher::Universal => mac.is_universal(),
+            AclMatcher::Broadcast => mac.is_broadcast(),
+        }
+    }
+}
+
+impl core::str::FromStr for AclMatcher {
+    type Err = MacParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let matcher = match input {
+            "any" => AclMatcher::Any,
+            "multicast" => AclMatcher::Multicast,
+            "unicast" => AclMatcher::Unicast,
+            "local" => AclMatcher::Local,
+            "universal" => AclMatcher::Universal,
+            "broadcast" => AclMatcher::Broadcast,
+            // a value and mask separated by whitespace
+            _ if input.contains(char::is_whitespace) => AclMatcher::Pattern(input.parse()?),
+            _ => match input.find('/') {
+                // a short suffix is a prefix length, a longer one a mask
+                Some(i) if input.len() - i <= 3 => AclMatcher::Prefix(input.parse()?),
+                Some(_) => AclMatcher::Pattern(input.parse()?),
+                None if input.contains(['*', '?']) => AclMatcher::Pattern(input.parse()?),
+                None => AclMatcher::Address(input.parse()?),
+            },
+        };
+
+        Ok(matcher)
+    }
+}
+
+impl core::fmt::Display for AclMatcher {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            AclMatcher::Any => f.write_str("any"),
+            AclMatcher::Address(addr) => addr.fmt(f),
+            AclMatcher::Prefix(prefix) => prefix.fmt(f),
+            AclMatcher::Pattern(pattern) => pattern.fmt(f),
+            AclMatcher::Multicast => f.write_str("multicast"),
+            AclMatcher::Unicast => f.write_str("unicast"),
+            AclMatcher::Local => f.write_str("local"),
+            AclMatcher::Universal => f.write_str("universal"),
+            AclMatcher::Broadcast => f.write_str("broadcast"),
+        }
+    }
+}
+
+/// A single ACL rule, written as the action followed by the matcher, e.g.
+/// `deny local` or `allow 00:11:22:00:00:00/24`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AclRule {
+    /// What to do with matching addresses.
+    pub action: AclAction,
+    /// Which addresses the rule applies to.
+    pub matcher: AclMatcher,
+}
+
+impl core::str::FromStr for AclRule {
+    type Err = AclParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let trimmed = input.trim_start();
+        let start = input.len() - trimmed.len();
+        let input = trimmed.trim_end();
+        let (action, matcher) = match input.find(char::is_whitespace) {
+            Some(i) => (&input[..i], input[i..].trim_start()),
+            None => (input, ""),
+        };
+        let matcher_start = start + input.len() - matcher.len();
+
+        let action = match action {
+            "allow" => AclAction::Allow,
+            "deny" => AclAction::Deny,
+            _ => return Err(AclParseError::new(AclParseErrorKind::InvalidAction)),
+        };
+
+        if matcher.is_empty() {
+            return Err(AclParseError::new(AclParseErrorKind::MissingMatcher));
+        }
+
+        let matcher = matcher.parse().map_err(|e: MacParseError| {
+            AclParseError::new(AclParseErrorKind::InvalidMatcher(
+                e.offset_by(matcher_start),
+            ))
+        })?;
+
+        Ok(AclRule { action, matcher })
+    }
+}
+
+impl core::fmt::Display for AclRule {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let action = match self.action {
+            AclAction::Allow => "allow",
+            AclAction::Deny => "deny",
+        };
+
+        write!(f, "{} {}", action, self.matcher)
+    }
+}
+
+/// The outcome of checking an address against a [`MacAcl`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decision {
+    /// The action to take for the address.
+    pub action: AclAction,
+    /// The index of the first rule that matched, or `None` if no rule matched
+    /// and the default action applies.
+    pub rule: Option<usize>,
+}
+
+impl Decision {
+    /// Returns `true` if the address is allowed.
+    pub fn is_allowed(self) -> bool {
+        self.action == AclAction::Allow
+    }
+}
+
+/// An ordered list of allow and deny rules, where the first matching rule
+/// decides the outcome.
+///
+/// ACLs can be parsed from text with one [`AclRule`] per line. Blank lines and
+/// anything following a `#` are ignored:
+///
+/// ```text
+/// allow 00:11:22:33:44:55     # the lab printer
+/// deny local
+/// allow 00:11:22:00:00:00/24
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MacAcl {
+    rules: Vec<AclRule>,
+    default: AclAction,
+}
+
+impl MacAcl {
+    /// Creates an empty ACL that denies every address.
+    pub fn new() -> MacAcl {
+        MacAcl {
+            rules: Vec::new(),
+            default: AclAction::Deny,
+        }
+    }
+
+    /// Sets the action for addresses that don't match any rule, which is
+    /// [`AclAction::Deny`] unless changed.
+    pub fn set_default(&mut self, action: AclAction) {
+        self.default = action;
+    }
+
+    /// Appends a rule, which is checked after all existing rules.
+    pub fn push(&mut self, rule: AclRule) {
+        self.rules.push(rule);
+    }
+
+    /// Returns the rules in evaluation order.
+    pub fn rules(&self) -> &[AclRule] {
+        &self.rules
+    }
+
+    /// Checks `mac` against the rules in order, returning the action of the
+    /// first one that matches.
+    pub fn check(&self, mac: &MacAddress) -> Decision {
+        self.rules
+            .iter()
+            .position(|rule| rule.matcher.matches(mac))
+            .map(|i| Decision {
+                action: self.rules[i].action,
+                rule: Some(i),
+            })
+            .unwrap_or(Decision {
+                action: self.default,
+                rule: None,
+            })
+    }
+}
+
+impl Default for MacAcl {
+    fn default() -> Self {
+        MacAcl::new()
+    }
+}
+
+impl core::str::FromStr for MacAcl {
+    type Err = AclParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut acl = MacAcl::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let rule = line.parse::<AclRule>().map_err(|mut e| {
+                e.line = Some(i + 1);
+                e
+            })?;
+            acl.push(rule);
+        }
+
+        Ok(acl)
+    }
+}
+
+/// An error that may occur when parsing an ACL rule.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AclParseError {
+    line: Option<usize>,
+    kind: AclParseErrorKind,
+}
+
+/// The reason an ACL rule failed to parse.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AclParseErrorKind {
+    /// The rule didn't start with `allow` or `deny`.
+    InvalidAction,
+    /// The rule had an action but nothing to match.
+    MissingMatcher,
+    /// The matcher wasn't a keyword, address, prefix or pattern.
+    InvalidMatcher(MacParseError),
+}
+
+impl AclParseError {
+    fn new(kind: AclParseErrorKind) -> AclParseError {
+        AclParseError { line: None, kind }
+    }
+
+    /// Returns the 1-based line the error occurred on, when parsing a whole
+    /// [`MacAcl`]. Offsets in [`AclParseErrorKind::InvalidMatcher`] are
+    /// relative to the start of the line.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Returns the reason the rule failed to parse.
+    pub fn kind(&self) -> AclParseErrorKind {
+        self.kind
+    }
+}
+
+impl core::fmt::Display for AclParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+
+        match self.kind {
+            AclParseErrorKind::InvalidAction => f.write_str("expected `allow` or `deny`"),
+            AclParseErrorKind::MissingMatcher => f.write_str("missing matcher"),
+            AclParseErrorKind::InvalidMatcher(e) => write!(f, "invalid matcher: {}", e),
+        }
+    }
+}
+
+impl core::error::Error for AclParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_mac, ExpectedToken};
+
+    #[test]
+    fn first_match_wins() {
+        let acl: MacAcl = "
+            # vendors we trust, except for the odd device
+            deny 00:11:22:33:44:55
+            allow 00:11:22:00:00:00/24
+            deny local
+            allow 02:00:00:00:00:01
+            allow 00:AA:BB:*:*:*
+        "
+        .parse()
+        .unwrap();
+
+        let check = |s: &str| acl.check(&test_mac(s));
+
+        assert_eq!(
+            check("00:11:22:33:44:55"),
+            Decision {
+                action: AclAction::Deny,
+                rule: Some(0)
+            }
+        );
+        assert!(check("00:11:22:33:44:56").is_allowed());
+        assert_eq!(check("02:00:00:00:00:01").rule, Some(2));
+        assert_eq!(check("00:AA:BB:01:02:03").rule, Some(4));
+        assert_eq!(
+            check("48:27:E2:44:25:D8"),
+            Decision {
+                action: AclAction::Deny,
+                rule: None
+            }
+        );
+    }
+
+    #[test]
+    fn default_action() {
+        let mut acl = MacAcl::new();
+        acl.push("deny multicast".parse().unwrap());
+        acl.set_default(AclAction::Allow);
+
+        assert!(!acl.check(&MacAddress::BROADCAST).is_allowed());
+        assert!(acl.check(&test_mac("48:27:E2:44:25:D8")).is_allowed());
+    }
+
+    #[test]
+    fn rule_round_trip() {
+        for rule in &[
+            "allow any",
+            "deny broadcast",
+            "allow universal",
+            "allow 00:11:22:33:44:55",
+            "deny 00:11:22:00:00:00/24",
+            "allow 00:11:22:3?:*:*",
+            "deny 01:00:00:00:00:00/01:00:00:00:00:00",
+        ] {
+            assert_eq!(rule.parse::<AclRule>().unwrap().to_string(), *rule);
+        }
+
+        let rule = "allow 00:11:22:00:00:00 ff:ff:ff:00:00:00"
+            .parse::<AclRule>()
+            .unwrap();
+        assert_eq!(rule.to_string(), "allow 00:11:22:*:*:*");
+        let rule = "deny 01:00:00:00:00:00\t01:00:00:00:00:00"
+            .parse::<AclRule>()
+            .unwrap();
+        assert_eq!(rule.to_string(), "deny 01:00:00:00:00:00/01:00:00:00:00:00");
+    }
+
+    #[test]
+    fn errors() {
+        let err = "allow any\npermit local".parse::<MacAcl>().unwrap_err();
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.kind(), AclParseErrorKind::InvalidAction);
+        assert_eq!(err.to_string(), "line 2: expected `allow` or `deny`");
+
+        let err = "deny".parse::<AclRule>().unwrap_err();
+        assert_eq!(err.kind(), AclParseErrorKind::MissingMatcher);
+
+        let err = "deny 00:11:22:33:44".parse::<AclRule>().unwrap_err();
+        assert_eq!(
+            err.kind(),
+            AclParseErrorKind::InvalidMatcher(MacParseError::InvalidLength)
+        );
+
+        let err = "deny 00:11:22:00:00:00 ff:ff:ZZ:00:00:00"
+            .parse::<AclRule>()
+            .unwrap_err();
+        match err.kind() {
+            AclParseErrorKind::InvalidMatcher(e) => assert_eq!(e.offset(), Some(29)),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let err = "deny 00:11:22:00:00:00/2x".parse::<AclRule>().unwrap_err();
+        assert_eq!(
+            err.kind(),
+            AclParseErrorKind::InvalidMatcher(MacParseError::UnexpectedChar {
+                offset: 24,
+                found: 'x',
+                expected: ExpectedToken::DecimalDigit
+            })
+        );
+
+        let err = "deny 00:11:22:ZZ:00:00".parse::<AclRule>().unwrap_err();
+        assert_eq!(
+            err.kind(),
+            AclParseErrorKind::InvalidMatcher(MacParseError::UnexpectedChar {
+                offset: 14,
+                found: 'Z',
+                expected: ExpectedToken::HexDigit
+            })
+        );
+
+        let err = "allow any\n  deny\t00:11:22:*:*:Z? # guests"
+            .parse::<MacAcl>()
+            .unwrap_err();
+        assert_eq!(err.line(), Some(2));
+        match err.kind() {
+            AclParseErrorKind::InvalidMatcher(e) => assert_eq!(e.offset(), Some(20)),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+}
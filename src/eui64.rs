@@ -0,0 +1,231 @@
+use crate::parse::parse_hex_groups;
+use crate::{MacAddress, MacParseError, ParseOptions};
+
+/// Contains the individual bytes of a 64-bit Extended Unique Identifier, as
+/// used by IEEE 802.15.4 (Zigbee, Thread) devices and IPv6 interface
+/// identifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "alloc::borrow::Cow<'_, str>"))]
+pub struct Eui64 {
+    bytes: [u8; 8],
+}
+
+impl Eui64 {
+    /// Creates a new `Eui64` struct from the given bytes.
+    pub fn new(bytes: [u8; 8]) -> Eui64 {
+        Eui64 { bytes }
+    }
+
+    /// Returns the array of EUI-64 bytes.
+    pub fn bytes(self) -> [u8; 8] {
+        self.bytes
+    }
+
+    /// Creates an EUI-64 from an EUI-48 by inserting `FF:FE` between the OUI
+    /// and the device-specific bytes.
+    pub fn from_eui48(mac: MacAddress) -> Eui64 {
+        let b = mac.bytes();
+        Eui64::new([b[0], b[1], b[2], 0xFF, 0xFE, b[3], b[4], b[5]])
+    }
+
+    /// Returns the EUI-48 this address was derived from, if it contains the
+    /// `FF:FE` marker inserted by [`Eui64::from_eui48`].
+    pub fn to_eui48(self) -> Option<MacAddress> {
+        let b = self.bytes;
+
+        if b[3] == 0xFF && b[4] == 0xFE {
+            Some(MacAddress::new([b[0], b[1], b[2], b[5], b[6], b[7]]))
+        } else {
+            None
+        }
+    }
+
+    /// Creates an `Eui64` from an IEEE 802.15.4 extended address as it appears
+    /// on the air, which is least significant byte first.
+    pub fn from_ieee802154_bytes(mut bytes: [u8; 8]) -> Eui64 {
+        bytes.reverse();
+        Eui64::new(bytes)
+    }
+
+    /// Returns the address in IEEE 802.15.4 over-the-air byte order, least
+    /// significant byte first.
+    pub fn to_ieee802154_bytes(self) -> [u8; 8] {
+        let mut bytes = self.bytes;
+        bytes.reverse();
+        bytes
+    }
+
+    /// Returns the Organizationally Unique Identifier, the first three bytes of
+    /// the address.
+    pub fn oui(self) -> [u8; 3] {
+        [self.bytes[0], self.bytes[1], self.bytes[2]]
+    }
+
+    /// Returns `true` if the I/G bit is set, meaning this is a group
+    /// (multicast) address.
+    pub fn is_multicast(self) -> bool {
+        self.bytes[0] & 0x01 != 0
+    }
+
+    /// Returns `true` if the I/G bit is clear, meaning this is an individual
+    /// (unicast) address.
+    pub fn is_unicast(self) -> bool {
+        !self.is_multicast()
+    }
+
+    /// Returns `true` if the U/L bit is set, meaning this address is locally
+    /// administered.
+    pub fn is_local(self) -> bool {
+        self.bytes[0] & 0x02 != 0
+    }
+
+    /// Returns `true` if the U/L bit is clear, meaning this address is
+    /// universally administered (assigned by the manufacturer).
+    pub fn is_universal(self) -> bool {
+        !self.is_local()
+    }
+
+    /// Returns `true` if this is the broadcast address
+    /// `FF:FF:FF:FF:FF:FF:FF:FF`.
+    pub fn is_broadcast(self) -> bool {
+        self.bytes == [0xFF; 8]
+    }
+
+    /// Returns `true` if every byte of the address is zero.
+    pub fn is_nil(self) -> bool {
+        self.bytes == [0; 8]
+    }
+}
+
+impl From<[u8; 8]> for Eui64 {
+    fn from(v: [u8; 8]) -> Self {
+        Eui64::new(v)
+    }
+}
+
+impl From<MacAddress> for Eui64 {
+    fn from(mac: MacAddress) -> Self {
+        Eui64::from_eui48(mac)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Eui64 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl core::str::FromStr for Eui64 {
+    type Err = MacParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut array = [0u8; 8];
+        parse_hex_groups(input, &mut array, ParseOptions::new())?;
+
+        Ok(Eui64::new(array))
+    }
+}
+
+impl core::convert::TryFrom<&'_ str> for Eui64 {
+    type Error = MacParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::convert::TryFrom<alloc::borrow::Cow<'_, str>> for Eui64 {
+    type Error = MacParseError;
+
+    fn try_from(value: alloc::borrow::Cow<'_, str>) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl core::fmt::Display for Eui64 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut buf = [0u8; 23];
+        f.pad(crate::format::encode_hex(
+            &self.bytes,
+            &mut buf,
+            Some(':'),
+            1,
+            true,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        let string = "00:12:4B:00:1C:A1:B2:C3";
+        let eui = string.parse::<Eui64>().unwrap();
+        assert_eq!(
+            eui.bytes(),
+            [0x00, 0x12, 0x4B, 0x00, 0x1C, 0xA1, 0xB2, 0xC3]
+        );
+        assert_eq!(eui.to_string(), string);
+
+        assert_eq!("00-12-4b-00-1c-a1-b2-c3".parse::<Eui64>().unwrap(), eui);
+        assert_eq!("00124b001ca1b2c3".parse::<Eui64>().unwrap(), eui);
+        assert_eq!(
+            "00:12:4B:00:1C:A1".parse::<Eui64>().unwrap_err(),
+            MacParseError::InvalidLength
+        );
+        assert_eq!(
+            "00:12:4B:00:1C:A1:B2:ZZ".parse::<Eui64>().unwrap_err(),
+            MacParseError::UnexpectedChar {
+                offset: 21,
+                found: 'Z',
+                expected: crate::ExpectedToken::HexDigit
+            }
+        );
+    }
+
+    #[test]
+    fn eui48_round_trip() {
+        let mac = MacAddress::new([0x48, 0x27, 0xE2, 0x44, 0x25, 0xD8]);
+        let eui = Eui64::from(mac);
+        assert_eq!(eui.to_string(), "48:27:E2:FF:FE:44:25:D8");
+        assert_eq!(eui.to_eui48(), Some(mac));
+
+        let native = Eui64::new([0x00, 0x12, 0x4B, 0x00, 0x1C, 0xA1, 0xB2, 0xC3]);
+        assert_eq!(native.to_eui48(), None);
+    }
+
+    #[test]
+    fn ieee802154_byte_order() {
+        let eui = Eui64::new([0x00, 0x12, 0x4B, 0x00, 0x1C, 0xA1, 0xB2, 0xC3]);
+        let air = eui.to_ieee802154_bytes();
+        assert_eq!(air, [0xC3, 0xB2, 0xA1, 0x1C, 0x00, 0x4B, 0x12, 0x00]);
+        assert_eq!(Eui64::from_ieee802154_bytes(air), eui);
+    }
+
+    #[test]
+    fn bits() {
+        let eui = Eui64::new([0x02, 0x12, 0x4B, 0x00, 0x1C, 0xA1, 0xB2, 0xC3]);
+        assert!(eui.is_local());
+        assert!(eui.is_unicast());
+        assert_eq!(eui.oui(), [0x02, 0x12, 0x4B]);
+        assert!(Eui64::new([0xFF; 8]).is_broadcast());
+        assert!(Eui64::default().is_nil());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_works() {
+        use serde_test::{assert_tokens, Token};
+        let eui: Eui64 = "00:12:4B:00:1C:A1:B2:C3".parse().unwrap();
+
+        assert_tokens(&eui, &[Token::BorrowedStr("00:12:4B:00:1C:A1:B2:C3")]);
+    }
+}
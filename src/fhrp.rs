@@ -0,0 +1,144 @@
+use crate::MacAddress;
+
+/// A first hop redundancy protocol virtual router address, as returned by
+/// [`MacAddress::first_hop_redundancy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FirstHopRedundancy {
+    /// VRRP for IPv4 (`00:00:5E:00:01:XX`) with its virtual router ID. CARP
+    /// uses the same addresses, with the virtual host ID in place of the VRID.
+    VrrpV4(u8),
+    /// VRRP for IPv6 (`00:00:5E:00:02:XX`) with its virtual router ID.
+    VrrpV6(u8),
+    /// HSRP version 1 (`00:00:0C:07:AC:XX`) with its group number.
+    HsrpV1(u8),
+    /// HSRP version 2 for IPv4 (`00:00:0C:9F:FX:XX`) with its group number.
+    HsrpV2(u16),
+    /// HSRP for IPv6 (`00:05:73:A0:0X:XX`) with its group number.
+    HsrpV6(u16),
+}
+
+impl MacAddress {
+    /// Returns the virtual MAC address of the IPv4 VRRP (or CARP) router with
+    /// the given virtual router ID.
+    pub fn vrrp_v4(vrid: u8) -> MacAddress {
+        MacAddress::new([0x00, 0x00, 0x5E, 0x00, 0x01, vrid])
+    }
+
+    /// Returns the virtual MAC address of the IPv6 VRRP router with the given
+    /// virtual router ID.
+    pub fn vrrp_v6(vrid: u8) -> MacAddress {
+        MacAddress::new([0x00, 0x00, 0x5E, 0x00, 0x02, vrid])
+    }
+
+    /// Returns the virtual MAC address of the given HSRP version 1 group.
+    pub fn hsrp_v1(group: u8) -> MacAddress {
+        MacAddress::new([0x00, 0x00, 0x0C, 0x07, 0xAC, group])
+    }
+
+    /// Returns the virtual MAC address of the given HSRP version 2 group.
+    ///
+    /// Returns `None` if `group` is greater than 4095.
+    pub fn hsrp_v2(group: u16) -> Option<MacAddress> {
+        hsrp_12bit([0x00, 0x00, 0x0C, 0x9F, 0xF0], group)
+    }
+
+    /// Returns the virtual MAC address of the given IPv6 HSRP group.
+    ///
+    /// Returns `None` if `group` is greater than 4095.
+    pub fn hsrp_v6(group: u16) -> Option<MacAddress> {
+        hsrp_12bit([0x00, 0x05, 0x73, 0xA0, 0x00], group)
+    }
+
+    /// Identifies the protocol and group number if this is a first hop
+    /// redundancy protocol virtual MAC address.
+    pub fn first_hop_redundancy(self) -> Option<FirstHopRedundancy> {
+        let b = self.bytes();
+        let group = u16::from_be_bytes([b[4] & 0x0F, b[5]]);
+
+        let fhrp = match b {
+            [0x00, 0x00, 0x5E, 0x00, 0x01, vrid] => FirstHopRedundancy::VrrpV4(vrid),
+            [0x00, 0x00, 0x5E, 0x00, 0x02, vrid] => FirstHopRedundancy::VrrpV6(vrid),
+            [0x00, 0x00, 0x0C, 0x07, 0xAC, group] => FirstHopRedundancy::HsrpV1(group),
+            [0x00, 0x00, 0x0C, 0x9F, b4, _] if b4 & 0xF0 == 0xF0 => {
+                FirstHopRedundancy::HsrpV2(group)
+            }
+            [0x00, 0x05, 0x73, 0xA0, b4, _] if b4 & 0xF0 == 0x00 => {
+                FirstHopRedundancy::HsrpV6(group)
+            }
+            _ => return None,
+        };
+
+        Some(fhrp)
+    }
+}
+
+fn hsrp_12bit(prefix: [u8; 5], group: u16) -> Option<MacAddress> {
+    if group > 0x0FFF {
+        return None;
+    }
+
+    let [hi, lo] = group.to_be_bytes();
+    Some(MacAddress::new([
+        prefix[0],
+        prefix[1],
+        prefix[2],
+        prefix[3],
+        prefix[4] | hi,
+        lo,
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constructors() {
+        assert_eq!(MacAddress::vrrp_v4(10).to_string(), "00:00:5E:00:01:0A");
+        assert_eq!(MacAddress::vrrp_v6(10).to_string(), "00:00:5E:00:02:0A");
+        assert_eq!(MacAddress::hsrp_v1(1).to_string(), "00:00:0C:07:AC:01");
+        assert_eq!(
+            MacAddress::hsrp_v2(4095).unwrap().to_string(),
+            "00:00:0C:9F:FF:FF"
+        );
+        assert_eq!(
+            MacAddress::hsrp_v6(258).unwrap().to_string(),
+            "00:05:73:A0:01:02"
+        );
+        assert_eq!(MacAddress::hsrp_v2(4096), None);
+        assert_eq!(MacAddress::hsrp_v6(4096), None);
+    }
+
+    #[test]
+    fn recognize() {
+        assert_eq!(
+            MacAddress::vrrp_v4(7).first_hop_redundancy(),
+            Some(FirstHopRedundancy::VrrpV4(7))
+        );
+        assert_eq!(
+            MacAddress::vrrp_v6(7).first_hop_redundancy(),
+            Some(FirstHopRedundancy::VrrpV6(7))
+        );
+        assert_eq!(
+            MacAddress::hsrp_v1(200).first_hop_redundancy(),
+            Some(FirstHopRedundancy::HsrpV1(200))
+        );
+        assert_eq!(
+            MacAddress::hsrp_v2(1234).unwrap().first_hop_redundancy(),
+            Some(FirstHopRedundancy::HsrpV2(1234))
+        );
+        assert_eq!(
+            MacAddress::hsrp_v6(1234).unwrap().first_hop_redundancy(),
+            Some(FirstHopRedundancy::HsrpV6(1234))
+        );
+        assert_eq!(
+            MacAddress::new([0x00, 0x00, 0x5E, 0x00, 0x03, 0x01]).first_hop_redundancy(),
+            None
+        );
+        assert_eq!(
+            MacAddress::new([0x00, 0x05, 0x73, 0xA0, 0x10, 0x01]).first_hop_redundancy(),
+            None
+        );
+    }
+}
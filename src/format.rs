@@ -0,0 +1,302 @@
+use crate::MacAddress;
+use core::fmt::Write;
+
+/// A textual format for MAC addresses, used with [`MacAddress::format`].
+///
+/// The presets cover the formats used by common systems and network gear;
+/// [`MacFormat::new`] allows others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MacFormat {
+    prefix: &'static str,
+    separator: Option<char>,
+    group_bytes: usize,
+    uppercase: bool,
+}
+
+impl MacFormat {
+    /// Uppercase and colon-separated, `00:1A:2B:3C:4D:5E`. This is the format
+    /// used by the `Display` implementation of [`MacAddress`].
+    pub const COLON_UPPER: MacFormat = MacFormat::preset(Some(':'), 1, true);
+    /// Lowercase and colon-separated, `00:1a:2b:3c:4d:5e`, as printed by
+    /// Linux and most Unix tools.
+    pub const COLON_LOWER: MacFormat = MacFormat::preset(Some(':'), 1, false);
+    /// The IEEE 802 canonical format, uppercase and hyphen-separated,
+    /// `00-1A-2B-3C-4D-5E`.
+    pub const IEEE: MacFormat = MacFormat::preset(Some('-'), 1, true);
+    /// Cisco's dotted format, `001a.2b3c.4d5e`.
+    pub const CISCO: MacFormat = MacFormat::preset(Some('.'), 2, false);
+    /// Lowercase hex without separators, `001a2b3c4d5e`.
+    pub const BARE: MacFormat = MacFormat::preset(None, 6, false);
+    /// The format shown by Windows, `00-1A-2B-3C-4D-5E`.
+    pub const WINDOWS: MacFormat = MacFormat::IEEE;
+    /// PostgreSQL's `macaddr` output format, `00:1a:2b:3c:4d:5e`.
+    pub const POSTGRESQL: MacFormat = MacFormat::COLON_LOWER;
+    /// The RADIUS `Calling-Station-Id` format recommended by [RFC
+    /// 3580](https://www.rfc-editor.org/rfc/rfc3580#section-3.21),
+    /// `00-1A-2B-3C-4D-5E`.
+    pub const RADIUS: MacFormat = MacFormat::IEEE;
+    /// The PXELINUX configuration file name format, with the Ethernet ARP
+    /// hardware type first, `01-00-1a-2b-3c-4d-5e`.
+    pub const PXE: MacFormat = MacFormat {
+        prefix: "01-",
+        ..MacFormat::preset(Some('-'), 1, false)
+    };
+
+    /// Creates a format that writes the address in groups of `group_bytes`
+    /// bytes with `separator` between them.
+    ///
+    /// Returns `None` unless `group_bytes` is 1, 2, 3 or 6.
+    pub fn new(separator: Option<char>, group_bytes: usize, uppercase: bool) -> Option<MacFormat> {
+        match group_bytes {
+            1 | 2 | 3 | 6 => Some(MacFormat::preset(separator, group_bytes, uppercase)),
+            _ => None,
+        }
+    }
+
+    /// Returns this format with `prefix` written before the address.
+    pub fn with_prefix(self, prefix: &'static str) -> MacFormat {
+        MacFormat { prefix, ..self }
+    }
+
+    const fn preset(separator: Option<char>, group_bytes: usize, uppercase: bool) -> MacFormat {
+        MacFormat {
+            prefix: "",
+            separator,
+            group_bytes,
+            uppercase,
+        }
+    }
+}
+
+/// A [`MacAddress`] paired with a [`MacFormat`], which formats the address
+/// accordingly when displayed. Returned by [`MacAddress::format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormattedMacAddress {
+    mac: MacAddress,
+    format: MacFormat,
+}
+
+impl MacAddress {
+    /// Returns an adapter that displays the address in the given format.
+    pub fn format(self, format: MacFormat) -> FormattedMacAddress {
+        FormattedMacAddress { mac: self, format }
+    }
+}
+
+impl core::fmt::Display for FormattedMacAddress {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let format = self.format;
+        let mut buf = [0u8; 32];
+        let hex = encode_hex(
+            &self.mac.bytes(),
+            &mut buf,
+            format.separator,
+            format.group_bytes,
+            format.uppercase,
+        );
+
+        pad_parts(f, &[format.prefix, hex])
+    }
+}
+
+/// Writes `bytes` into `buf` as hex digits, with `separator` between every
+/// `group_bytes` bytes, and returns the written portion of `buf`.
+///
+/// `buf` must be large enough for the digits and separators, which is
+/// checked by the callers' fixed buffer sizes.
+pub(crate) fn encode_hex<'a>(
+    bytes: &[u8],
+    buf: &'a mut [u8],
+    separator: Option<char>,
+    group_bytes: usize,
+    uppercase: bool,
+) -> &'a str {
+    let digits = if uppercase {
+        b"0123456789ABCDEF"
+    } else {
+        b"0123456789abcdef"
+    };
+    let mut sep = [0u8; 4];
+    let sep = separator.map_or(&[][..], |c| c.encode_utf8(&mut sep).as_bytes());
+
+    let mut len = 0;
+    for (i, b) in bytes.iter().enumerate() {
+        if i != 0 && i % group_bytes == 0 {
+            buf[len..len + sep.len()].copy_from_slice(sep);
+            len += sep.len();
+        }
+
+        buf[len] = digits[usize::from(b >> 4)];
+        buf[len + 1] = digits[usize::from(b & 0x0F)];
+        len += 2;
+    }
+
+    // only ASCII digits and whole UTF-8 separators were written
+    core::str::from_utf8(&buf[..len]).unwrap_or_default()
+}
+
+/// Writes the concatenation of `parts`, padded and truncated according to the
+/// formatter's width, fill, alignment and precision like
+/// [`core::fmt::Formatter::pad`].
+fn pad_parts(f: &mut core::fmt::Formatter, parts: &[&str]) -> core::fmt::Result {
+    let precision = f.precision().unwrap_or(usize::MAX);
+
+    let mut len = 0;
+    for part in parts {
+        len += truncate(part, precision - len).chars().count();
+    }
+    let padding = f.width().unwrap_or(0).saturating_sub(len);
+
+    let (before, after) = match f.align() {
+        Some(core::fmt::Alignment::Right) => (padding, 0),
+        Some(core::fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+
+    let mut written = 0;
+    for part in parts {
+        let part = truncate(part, precision - written);
+        written += part.chars().count();
+        f.write_str(part)?;
+    }
+
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the first `chars` characters of `s`.
+fn truncate(s: &str, chars: usize) -> &str {
+    match s.char_indices().nth(chars) {
+        Some((end, _)) => &s[..end],
+        None => s,
+    }
+}
+
+/// Formats the address as lowercase colon-separated hex, or without
+/// separators with the alternate flag (`{:#x}`).
+impl core::fmt::LowerHex for MacAddress {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let format = if f.alternate() {
+            MacFormat::BARE
+        } else {
+            MacFormat::COLON_LOWER
+        };
+
+        core::fmt::Display::fmt(&self.format(format), f)
+    }
+}
+
+/// Formats the address as uppercase colon-separated hex, or without
+/// separators with the alternate flag (`{:#X}`).
+impl core::fmt::UpperHex for MacAddress {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let format = if f.alternate() {
+            MacFormat {
+                uppercase: true,
+                ..MacFormat::BARE
+            }
+        } else {
+            MacFormat::COLON_UPPER
+        };
+
+        core::fmt::Display::fmt(&self.format(format), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets() {
+        let mac = MacAddress::new([0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]);
+        let format = |f| mac.format(f).to_string();
+
+        assert_eq!(format(MacFormat::COLON_UPPER), mac.to_string());
+        assert_eq!(format(MacFormat::COLON_LOWER), "00:1a:2b:3c:4d:5e");
+        assert_eq!(format(MacFormat::IEEE), "00-1A-2B-3C-4D-5E");
+        assert_eq!(format(MacFormat::CISCO), "001a.2b3c.4d5e");
+        assert_eq!(format(MacFormat::BARE), "001a2b3c4d5e");
+        assert_eq!(format(MacFormat::WINDOWS), "00-1A-2B-3C-4D-5E");
+        assert_eq!(format(MacFormat::POSTGRESQL), "00:1a:2b:3c:4d:5e");
+        assert_eq!(format(MacFormat::RADIUS), "00-1A-2B-3C-4D-5E");
+        assert_eq!(format(MacFormat::PXE), "01-00-1a-2b-3c-4d-5e");
+    }
+
+    #[test]
+    fn custom() {
+        let mac = MacAddress::new([0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]);
+
+        let hp = MacFormat::new(Some('-'), 3, false).unwrap();
+        assert_eq!(mac.format(hp).to_string(), "001a2b-3c4d5e");
+
+        let prefixed = MacFormat::BARE.with_prefix("0x");
+        assert_eq!(mac.format(prefixed).to_string(), "0x001a2b3c4d5e");
+
+        let long = MacFormat::BARE.with_prefix("a prefix longer than thirty-two bytes: ");
+        assert_eq!(
+            format!("{:.42}", mac.format(long)),
+            "a prefix longer than thirty-two bytes: 001"
+        );
+        assert_eq!(
+            format!("{:>52}|", mac.format(long)),
+            " a prefix longer than thirty-two bytes: 001a2b3c4d5e|"
+        );
+        assert_eq!(format!("{:.1}", mac.format(long)), "a");
+
+        assert_eq!(MacFormat::new(None, 4, false), None);
+    }
+
+    #[test]
+    fn hex() {
+        let mac = MacAddress::new([0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]);
+
+        assert_eq!(format!("{:x}", mac), "00:1a:2b:3c:4d:5e");
+        assert_eq!(format!("{:X}", mac), "00:1A:2B:3C:4D:5E");
+        assert_eq!(format!("{:#x}", mac), "001a2b3c4d5e");
+        assert_eq!(format!("{:#X}", mac), "001A2B3C4D5E");
+    }
+
+    #[test]
+    fn padding() {
+        let mac = MacAddress::new([0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]);
+
+        assert_eq!(
+            format!("{:>16}", mac.format(MacFormat::CISCO)),
+            "  001a.2b3c.4d5e"
+        );
+        assert_eq!(
+            format!("{:-^24}", mac.format(MacFormat::PXE)),
+            "--01-00-1a-2b-3c-4d-5e--"
+        );
+        assert_eq!(
+            format!("{:<14}|", mac.format(MacFormat::BARE)),
+            "001a2b3c4d5e  |"
+        );
+        assert_eq!(format!("{:>19x}", mac), "  00:1a:2b:3c:4d:5e");
+
+        let wide = MacFormat::new(Some('→'), 2, true).unwrap();
+        assert_eq!(format!("{:>16}", mac.format(wide)), "  001A→2B3C→4D5E");
+    }
+
+    #[test]
+    fn precision() {
+        let mac = MacAddress::new([0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]);
+
+        assert_eq!(format!("{:.5}", mac), "00:1A");
+        assert_eq!(format!("{:.5}", mac.format(MacFormat::BARE)), "001a2");
+        assert_eq!(format!("{:.5}", mac.format(MacFormat::PXE)), "01-00");
+        assert_eq!(format!("{:>8.4x}", mac), "    00:1");
+
+        let wide = MacFormat::new(Some('→'), 2, true).unwrap();
+        assert_eq!(format!("{:.6}", mac.format(wide)), "001A→2");
+    }
+}
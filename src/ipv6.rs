@@ -0,0 +1,258 @@
+use crate::{sha1, Eui64, MacAddress};
+use core::net::Ipv6Addr;
+
+/// The number of times the Linux kernel retries generating a stable address
+/// when the result is a reserved interface identifier (`idgen_retries`).
+const IDGEN_RETRIES: u8 = 3;
+
+/// The network interface identifier (`Net_Iface`) mixed into an RFC 7217
+/// stable address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NetIface<'a> {
+    /// The interface's permanent hardware address, as used by Linux. This is
+    /// all zeros for devices with a random address, such as veth or bridges.
+    Mac(MacAddress),
+    /// The interface's name, e.g. `eth0`.
+    Name(&'a str),
+}
+
+/// Generates an [RFC 7217](https://www.rfc-editor.org/rfc/rfc7217) stable,
+/// semantically opaque IPv6 address within the given `/64` prefix.
+///
+/// This is the algorithm Linux uses for `addr_gen_mode` 2 and 3
+/// (`stable_secret`), so given the interface's `Net_Iface`, the configured
+/// secret and the DAD counter (normally 0), it predicts the address the host
+/// will assign itself. The interface identifier is the first 64 bits of a
+/// single SHA-1 block transform over the secret, prefix, `Net_Iface` (at most
+/// 32 bytes) and DAD counter, with the digest words laid out in little-endian
+/// order as on x86 and ARM hosts. The optional `Network_ID` isn't used.
+///
+/// Returns `None` if every attempt produced a reserved interface identifier.
+pub fn stable_privacy_address(
+    prefix: Ipv6Addr,
+    iface: NetIface<'_>,
+    secret: Ipv6Addr,
+    mut dad_counter: u8,
+) -> Option<Ipv6Addr> {
+    let mac;
+    let iface = match iface {
+        NetIface::Mac(m) => {
+            mac = m.bytes();
+            &mac[..]
+        }
+        NetIface::Name(name) => name.as_bytes(),
+    };
+    let iface = &iface[..iface.len().min(32)];
+
+    let mut octets = prefix.octets();
+
+    loop {
+        let mut block = [0u8; 64];
+        block[..16].copy_from_slice(&secret.octets());
+        block[16..24].copy_from_slice(&octets[..8]);
+        block[24..24 + iface.len()].copy_from_slice(iface);
+        block[56] = dad_counter;
+
+        let mut digest = sha1::INITIAL_STATE;
+        sha1::transform(&mut digest, &block);
+
+        octets[8..12].copy_from_slice(&digest[0].to_le_bytes());
+        octets[12..].copy_from_slice(&digest[1].to_le_bytes());
+
+        if !is_reserved_interface_id(&octets) {
+            return Some(Ipv6Addr::from(octets));
+        }
+
+        dad_counter += 1;
+        if dad_counter > IDGEN_RETRIES {
+            return None;
+        }
+    }
+}
+
+/// Checks for the reserved interface identifiers of [RFC
+/// 5453](https://www.rfc-editor.org/rfc/rfc5453), which a stable address must
+/// not use.
+///
+/// This matches the kernel's `ipv6_reserved_interfaceid`, which also treats
+/// `0200:5EFF:FFxx:xxxx` as reserved, although RFC 5453 only reserves
+/// `0200:5EFF:FExx:xxxx`.
+fn is_reserved_interface_id(octets: &[u8; 16]) -> bool {
+    let iid = &octets[8..];
+
+    // subnet-router anycast
+    iid == [0; 8]
+        // proxy mobile IPv6
+        || (iid[..4] == [0x02, 0x00, 0x5E, 0xFF] && iid[4] & 0xFE == 0xFE)
+        // reserved subnet anycast
+        || (iid[..7] == [0xFD, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF] && iid[7] & 0x80 != 0)
+}
+
+impl MacAddress {
+    /// Returns the modified EUI-64 interface identifier for this address, as
+    /// described in [RFC 4291, Appendix
+    /// A](https://www.rfc-editor.org/rfc/rfc4291#appendix-A): `FF:FE` is
+    /// inserted in the middle of the address and the U/L bit is inverted.
+    pub fn to_modified_eui64(self) -> Eui64 {
+        let mut bytes = Eui64::from_eui48(self).bytes();
+        bytes[0] ^= 0x02;
+        Eui64::new(bytes)
+    }
+
+    /// Returns the `fe80::/64` link-local IPv6 address derived from this MAC
+    /// address.
+    pub fn ipv6_link_local(self) -> Ipv6Addr {
+        self.ipv6_with_prefix(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0))
+    }
+
+    /// Returns the stateless autoconfiguration address formed from the given
+    /// prefix and this address's modified EUI-64 interface identifier.
+    ///
+    /// Returns `None` if `len` isn't 64, as [RFC
+    /// 4862](https://www.rfc-editor.org/rfc/rfc4862#section-5.5.3) requires
+    /// the prefix and interface identifier lengths to add up to 128 bits.
+    pub fn ipv6_slaac(self, prefix: Ipv6Addr, len: u8) -> Option<Ipv6Addr> {
+        if len != 64 {
+            return None;
+        }
+
+        Some(self.ipv6_with_prefix(prefix))
+    }
+
+    /// Recovers the MAC address embedded in an IPv6 address whose interface
+    /// identifier is a modified EUI-64, undoing [`MacAddress::ipv6_slaac`].
+    ///
+    /// Returns `None` if the interface identifier doesn't contain the `FF:FE`
+    /// marker, e.g. for temporary or stable opaque addresses.
+    pub fn from_ipv6_eui64(addr: Ipv6Addr) -> Option<MacAddress> {
+        let octets = addr.octets();
+        let mut iid = [0u8; 8];
+        iid.copy_from_slice(&octets[8..]);
+        iid[0] ^= 0x02;
+
+        Eui64::new(iid).to_eui48()
+    }
+
+    fn ipv6_with_prefix(self, prefix: Ipv6Addr) -> Ipv6Addr {
+        let mut octets = prefix.octets();
+        octets[8..].copy_from_slice(&self.to_modified_eui64().bytes());
+        Ipv6Addr::from(octets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modified_eui64() {
+        let mac = MacAddress::new([0x00, 0x1B, 0x2C, 0x03, 0x04, 0x05]);
+        assert_eq!(
+            mac.to_modified_eui64().bytes(),
+            [0x02, 0x1B, 0x2C, 0xFF, 0xFE, 0x03, 0x04, 0x05]
+        );
+
+        let local = MacAddress::new([0x52, 0x54, 0x00, 0x12, 0x34, 0x56]);
+        assert_eq!(
+            local.to_modified_eui64().bytes(),
+            [0x50, 0x54, 0x00, 0xFF, 0xFE, 0x12, 0x34, 0x56]
+        );
+    }
+
+    #[test]
+    fn link_local() {
+        let mac = MacAddress::new([0x52, 0x54, 0x00, 0x12, 0x34, 0x56]);
+        assert_eq!(
+            mac.ipv6_link_local(),
+            "fe80::5054:ff:fe12:3456".parse::<Ipv6Addr>().unwrap()
+        );
+    }
+
+    #[test]
+    fn slaac() {
+        let mac = MacAddress::new([0x00, 0x1B, 0x2C, 0x03, 0x04, 0x05]);
+        let prefix = "2001:db8:1:2::".parse::<Ipv6Addr>().unwrap();
+        assert_eq!(
+            mac.ipv6_slaac(prefix, 64),
+            Some("2001:db8:1:2:21b:2cff:fe03:405".parse().unwrap())
+        );
+        assert_eq!(mac.ipv6_slaac(prefix, 48), None);
+    }
+
+    #[test]
+    fn from_ipv6() {
+        let mac = MacAddress::new([0x00, 0x1B, 0x2C, 0x03, 0x04, 0x05]);
+        let addr = mac.ipv6_slaac("2001:db8::".parse().unwrap(), 64).unwrap();
+        assert_eq!(MacAddress::from_ipv6_eui64(addr), Some(mac));
+        assert_eq!(
+            MacAddress::from_ipv6_eui64("fe80::5054:ff:fe12:3456".parse().unwrap()),
+            Some(MacAddress::new([0x52, 0x54, 0x00, 0x12, 0x34, 0x56]))
+        );
+
+        let opaque = "2001:db8::8c3b:1d9e:41f7:a02c".parse().unwrap();
+        assert_eq!(MacAddress::from_ipv6_eui64(opaque), None);
+    }
+
+    #[test]
+    fn stable_privacy() {
+        let prefix = "2001:db8:1:2::".parse().unwrap();
+        let secret = "1111:2222:3333:4444:5555:6666:7777:8888".parse().unwrap();
+        let mac = MacAddress::new([0x52, 0x54, 0x00, 0x12, 0x34, 0x56]);
+
+        let a = stable_privacy_address(prefix, NetIface::Mac(mac), secret, 0).unwrap();
+        assert_eq!(a.segments()[..4], [0x2001, 0xdb8, 1, 2]);
+        assert_eq!(
+            stable_privacy_address(prefix, NetIface::Mac(mac), secret, 0),
+            Some(a)
+        );
+        assert_eq!(MacAddress::from_ipv6_eui64(a), None);
+
+        let other_prefix = "2001:db8:1:3::".parse().unwrap();
+        let other_secret = "::1".parse().unwrap();
+        assert_ne!(
+            stable_privacy_address(other_prefix, NetIface::Mac(mac), secret, 0),
+            Some(a)
+        );
+        assert_ne!(
+            stable_privacy_address(prefix, NetIface::Mac(mac), other_secret, 0),
+            Some(a)
+        );
+        assert_ne!(
+            stable_privacy_address(prefix, NetIface::Mac(mac), secret, 1),
+            Some(a)
+        );
+        assert_ne!(
+            stable_privacy_address(prefix, NetIface::Name("eth0"), secret, 0),
+            Some(a)
+        );
+    }
+
+    #[test]
+    fn stable_privacy_linux() {
+        // the link-local address Linux 6.18 (x86_64) assigned to a veth with
+        // this `stable_secret` and `addr_gen_mode` 2; veth addresses are
+        // random, so the permanent address the kernel hashes is all zeros
+        let secret = "1111:2222:3333:4444:5555:6666:7777:8888".parse().unwrap();
+        assert_eq!(
+            stable_privacy_address(
+                "fe80::".parse().unwrap(),
+                NetIface::Mac(MacAddress::new([0; 6])),
+                secret,
+                0
+            ),
+            Some("fe80::82ac:ee40:bc3b:1c74".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn reserved_interface_ids() {
+        let reserved = |s: &str| is_reserved_interface_id(&s.parse::<Ipv6Addr>().unwrap().octets());
+        assert!(reserved("2001:db8::"));
+        assert!(reserved("2001:db8::200:5eff:fe00:5212"));
+        assert!(reserved("2001:db8::200:5eff:ff00:5212"));
+        assert!(!reserved("2001:db8::200:5eff:fd00:5212"));
+        assert!(reserved("2001:db8::fdff:ffff:ffff:ff80"));
+        assert!(!reserved("2001:db8::fdff:ffff:ffff:ff7f"));
+        assert!(!reserved("2001:db8::1"));
+    }
+}
@@ -3,27 +3,84 @@
 //! entry](https://en.wikipedia.org/wiki/MAC_address) for more information.
 //!
 //! Supported platforms: Linux, Windows, MacOS, FreeBSD, NetBSD
+//!
+//! The `std` feature, enabled by default, provides the functions that query
+//! the operating system along with the types that need a hash map. Without it
+//! the crate is `no_std`, and the `alloc` feature enables the types that need
+//! an allocator, such as `MacAcl` and `MacPool`.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 
-#[cfg(target_os = "windows")]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(all(feature = "std", target_os = "windows"))]
 #[path = "windows.rs"]
 mod os;
 
-#[cfg(any(
-    target_os = "linux",
-    target_os = "macos",
-    target_os = "freebsd",
-    target_os = "netbsd",
-    target_os = "openbsd",
-    target_os = "android",
-    target_os = "illumos",
+#[cfg(all(
+    feature = "std",
+    any(
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "android",
+        target_os = "illumos",
+    )
 ))]
 #[path = "linux.rs"]
 mod os;
 
+#[cfg(feature = "alloc")]
+mod acl;
+mod eui64;
+mod fhrp;
+mod format;
+mod ipv6;
+#[cfg(feature = "std")]
 mod iter;
+mod multicast;
+mod parse;
+mod pattern;
+#[cfg(feature = "alloc")]
+mod pool;
+mod prefix;
+mod random;
+mod range;
+mod redact;
+mod scan;
+mod sha1;
+mod siphash;
+#[cfg(feature = "std")]
+mod table;
+mod virtualization;
+mod well_known;
+#[cfg(feature = "alloc")]
+pub use acl::{AclAction, AclMatcher, AclParseError, AclParseErrorKind, AclRule, Decision, MacAcl};
+pub use eui64::Eui64;
+pub use fhrp::FirstHopRedundancy;
+pub use format::{FormattedMacAddress, MacFormat};
+pub use ipv6::{stable_privacy_address, NetIface};
+#[cfg(feature = "std")]
 pub use iter::MacAddressIterator;
+pub use parse::ParseOptions;
+pub use pattern::MacPattern;
+#[cfg(feature = "alloc")]
+pub use pool::MacPool;
+pub use prefix::MacPrefix;
+pub use random::MacGenerator;
+pub use range::{MacAddressRange, OutOfRangeError};
+#[cfg(feature = "alloc")]
+pub use redact::redact_mac_addresses;
+pub use redact::MacPseudonymizer;
+pub use scan::{find_mac_addresses, MacAddressMatch, MacAddressMatches};
+#[cfg(feature = "std")]
+pub use table::MacTable;
+pub use virtualization::Virtualization;
+pub use well_known::WellKnownAddress;
 
 /// Possible errors when attempting to retrieve a MAC address.
 ///
@@ -34,14 +91,17 @@ pub enum MacAddressError {
     InternalError,
 }
 
-#[cfg(any(
-    target_os = "linux",
-    target_os = "macos",
-    target_os = "freebsd",
-    target_os = "netbsd",
-    target_os = "openbsd",
-    target_os = "android",
-    target_os = "illumos",
+#[cfg(all(
+    feature = "std",
+    any(
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "android",
+        target_os = "illumos",
+    )
 ))]
 impl From<nix::Error> for MacAddressError {
     fn from(_: nix::Error) -> MacAddressError {
@@ -49,35 +109,132 @@ impl From<nix::Error> for MacAddressError {
     }
 }
 
-impl std::fmt::Display for MacAddressError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for MacAddressError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.write_str(match self {
             MacAddressError::InternalError => "Internal API error",
         })
     }
 }
 
-impl std::error::Error for MacAddressError {}
+impl core::error::Error for MacAddressError {}
 
 /// An error that may occur when parsing a MAC address string.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
 pub enum MacParseError {
     /// Parsing of the MAC address contained an invalid digit.
     InvalidDigit,
     /// The MAC address did not have the correct length.
     InvalidLength,
+    /// A character other than the one expected was found.
+    UnexpectedChar {
+        /// The byte offset of the character in the input.
+        offset: usize,
+        /// The character found.
+        found: char,
+        /// What was expected instead.
+        expected: ExpectedToken,
+    },
+    /// A separator differed from the first one, which is rejected by strict
+    /// parsing.
+    MixedSeparators {
+        /// The byte offset of the separator in the input.
+        offset: usize,
+        /// The separator found.
+        found: char,
+        /// The first separator in the input.
+        expected: char,
+    },
+    /// The input contained a non-ASCII character, which can't be part of a
+    /// MAC address.
+    NonAscii {
+        /// The byte offset of the character in the input.
+        offset: usize,
+        /// The character found.
+        found: char,
+    },
+}
+
+/// What the parser expected where [`MacParseError::UnexpectedChar`] occurred.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ExpectedToken {
+    /// A hex digit, `0-9`, `a-f` or `A-F`.
+    HexDigit,
+    /// A separator between groups of digits, such as `:`.
+    Separator,
+    /// A decimal digit, `0-9`, as in the length of a [`MacPrefix`].
+    DecimalDigit,
+}
+
+impl MacParseError {
+    /// Returns the byte offset in the input at which the error occurred, if
+    /// known.
+    pub fn offset(&self) -> Option<usize> {
+        match *self {
+            MacParseError::UnexpectedChar { offset, .. }
+            | MacParseError::MixedSeparators { offset, .. }
+            | MacParseError::NonAscii { offset, .. } => Some(offset),
+            _ => None,
+        }
+    }
+
+    /// Shifts the offset of the error by `by` bytes, for errors in a part of
+    /// a larger input.
+    pub(crate) fn offset_by(mut self, by: usize) -> MacParseError {
+        match &mut self {
+            MacParseError::UnexpectedChar { offset, .. }
+            | MacParseError::MixedSeparators { offset, .. }
+            | MacParseError::NonAscii { offset, .. } => *offset += by,
+            _ => {}
+        }
+
+        self
+    }
+}
+
+impl core::fmt::Display for MacParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            MacParseError::InvalidDigit => f.write_str("invalid digit"),
+            MacParseError::InvalidLength => f.write_str("invalid length"),
+            MacParseError::UnexpectedChar {
+                offset,
+                found,
+                expected,
+            } => write!(
+                f,
+                "unexpected character {:?} at offset {}, expected {}",
+                found, offset, expected
+            ),
+            MacParseError::MixedSeparators {
+                offset,
+                found,
+                expected,
+            } => write!(
+                f,
+                "mixed separators, found {:?} at offset {} after {:?}",
+                found, offset, expected
+            ),
+            MacParseError::NonAscii { offset, found } => {
+                write!(f, "non-ASCII character {:?} at offset {}", found, offset)
+            }
+        }
+    }
 }
 
-impl std::fmt::Display for MacParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for ExpectedToken {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.write_str(match *self {
-            MacParseError::InvalidDigit => "invalid digit",
-            MacParseError::InvalidLength => "invalid length",
+            ExpectedToken::HexDigit => "a hex digit",
+            ExpectedToken::Separator => "a separator",
+            ExpectedToken::DecimalDigit => "a decimal digit",
         })
     }
 }
 
-impl std::error::Error for MacParseError {}
+impl core::error::Error for MacParseError {}
 
 impl From<core::num::ParseIntError> for MacParseError {
     fn from(_: core::num::ParseIntError) -> Self {
@@ -88,14 +245,14 @@ impl From<core::num::ParseIntError> for MacParseError {
 /// Contains the individual bytes of the MAC address.
 #[derive(Debug, Clone, Copy, PartialEq, Default, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
-#[cfg_attr(feature = "serde", serde(try_from = "std::borrow::Cow<'_, str>"))]
+#[cfg_attr(feature = "serde", serde(try_from = "alloc::borrow::Cow<'_, str>"))]
 pub struct MacAddress {
     bytes: [u8; 6],
 }
 
 impl MacAddress {
     /// Creates a new `MacAddress` struct from the given bytes.
-    pub fn new(bytes: [u8; 6]) -> MacAddress {
+    pub const fn new(bytes: [u8; 6]) -> MacAddress {
         MacAddress { bytes }
     }
 }
@@ -118,6 +275,7 @@ impl serde::Serialize for MacAddress {
 
 /// Calls the OS-specific function for retrieving the MAC address of the first
 /// network device containing one, ignoring local-loopback.
+#[cfg(feature = "std")]
 pub fn get_mac_address() -> Result<Option<MacAddress>, MacAddressError> {
     let bytes = os::get_mac(None)?;
 
@@ -127,6 +285,7 @@ pub fn get_mac_address() -> Result<Option<MacAddress>, MacAddressError> {
 /// Attempts to look up the MAC address of an interface via the specified name.
 /// **NOTE**: On Windows, this uses the `FriendlyName` field of the adapter, which
 /// is the same name shown in the "Network Connections" Control Panel screen.
+#[cfg(feature = "std")]
 pub fn mac_address_by_name(name: &str) -> Result<Option<MacAddress>, MacAddressError> {
     let bytes = os::get_mac(Some(name))?;
 
@@ -134,6 +293,7 @@ pub fn mac_address_by_name(name: &str) -> Result<Option<MacAddress>, MacAddressE
 }
 
 /// Attempts to look up the interface name via MAC address.
+#[cfg(feature = "std")]
 pub fn name_by_mac_address(mac: &MacAddress) -> Result<Option<String>, MacAddressError> {
     os::get_ifname(&mac.bytes)
 }
@@ -143,52 +303,63 @@ impl MacAddress {
     pub fn bytes(self) -> [u8; 6] {
         self.bytes
     }
+
+    /// Writes the address into `buf` in the same uppercase colon-separated
+    /// format as `Display`, e.g. `00:1A:2B:3C:4D:5E`, without allocating.
+    pub fn to_str_buf(self, buf: &mut [u8; 17]) -> &str {
+        format::encode_hex(&self.bytes, buf, Some(':'), 1, true)
+    }
+
+    /// Returns the Organizationally Unique Identifier, the first three bytes of
+    /// the address.
+    pub fn oui(self) -> [u8; 3] {
+        [self.bytes[0], self.bytes[1], self.bytes[2]]
+    }
+
+    /// Returns `true` if the I/G bit is set, meaning this is a group
+    /// (multicast) address.
+    pub fn is_multicast(self) -> bool {
+        self.bytes[0] & 0x01 != 0
+    }
+
+    /// Returns `true` if the I/G bit is clear, meaning this is an individual
+    /// (unicast) address.
+    pub fn is_unicast(self) -> bool {
+        !self.is_multicast()
+    }
+
+    /// Returns `true` if the U/L bit is set, meaning this address is locally
+    /// administered.
+    pub fn is_local(self) -> bool {
+        self.bytes[0] & 0x02 != 0
+    }
+
+    /// Returns `true` if the U/L bit is clear, meaning this address is
+    /// universally administered (assigned by the manufacturer).
+    pub fn is_universal(self) -> bool {
+        !self.is_local()
+    }
+
+    /// Returns `true` if this is the broadcast address `FF:FF:FF:FF:FF:FF`.
+    pub fn is_broadcast(self) -> bool {
+        self.bytes == [0xFF; 6]
+    }
+
+    /// Returns `true` if every byte of the address is zero.
+    pub fn is_nil(self) -> bool {
+        self.bytes == [0; 6]
+    }
 }
 
-impl std::str::FromStr for MacAddress {
+impl core::str::FromStr for MacAddress {
     type Err = MacParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut array = [0u8; 6];
-
-        // expect the `str` to be ASCII since it'll probably fail to parse
-        // anyway, this also asserts that each character in the string is only
-        // one byte in length which is necessary for the `match` below
-        if !input.is_ascii() {
-            // kind of hacky, but without `#[non_exhaustive]` on `MacParseError`
-            // adding a new variant is technically a breaking change, ugh...
-            return Err(MacParseError::InvalidLength);
-        }
-
-        match input.len() {
-            // MAC address with separators, e.g. 00:11:22:33:44:55
-            17 => {
-                array
-                    .iter_mut()
-                    .zip(input.split(|c| c == ':' || c == '-'))
-                    .try_for_each::<_, Result<(), MacParseError>>(|(b, s)| {
-                        *b = u8::from_str_radix(s, 16)?;
-                        Ok(())
-                    })?;
-            }
-            // MAC address without separators, e.g. 001122334455
-            12 => {
-                array
-                    .iter_mut()
-                    .zip((0..6).map(|i| &input[i * 2..=i * 2 + 1]))
-                    .try_for_each::<_, Result<(), MacParseError>>(|(b, s)| {
-                        *b = u8::from_str_radix(s, 16)?;
-                        Ok(())
-                    })?;
-            }
-            _ => return Err(MacParseError::InvalidLength),
-        }
-
-        Ok(MacAddress::new(array))
+        MacAddress::parse_with(input, ParseOptions::new())
     }
 }
 
-impl std::convert::TryFrom<&'_ str> for MacAddress {
+impl core::convert::TryFrom<&'_ str> for MacAddress {
     type Error = MacParseError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
@@ -196,31 +367,28 @@ impl std::convert::TryFrom<&'_ str> for MacAddress {
     }
 }
 
-impl std::convert::TryFrom<std::borrow::Cow<'_, str>> for MacAddress {
+#[cfg(feature = "alloc")]
+impl core::convert::TryFrom<alloc::borrow::Cow<'_, str>> for MacAddress {
     type Error = MacParseError;
 
-    fn try_from(value: std::borrow::Cow<'_, str>) -> Result<Self, Self::Error> {
+    fn try_from(value: alloc::borrow::Cow<'_, str>) -> Result<Self, Self::Error> {
         value.parse()
     }
 }
 
-impl std::fmt::Display for MacAddress {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let _ = write!(
-            f,
-            "{:<02X}:{:<02X}:{:<02X}:{:<02X}:{:<02X}:{:<02X}",
-            self.bytes[0],
-            self.bytes[1],
-            self.bytes[2],
-            self.bytes[3],
-            self.bytes[4],
-            self.bytes[5]
-        );
-
-        Ok(())
+impl core::fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut buf = [0u8; 17];
+        f.pad(self.to_str_buf(&mut buf))
     }
 }
 
+/// Parses a MAC address for use in tests, panicking if it's invalid.
+#[cfg(test)]
+pub(crate) fn test_mac(s: &str) -> MacAddress {
+    s.parse().unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -261,24 +429,65 @@ mod tests {
         let string = "80FA5B41";
         let address = string.parse::<MacAddress>().unwrap_err();
         assert_eq!(MacParseError::InvalidLength, address);
+    }
 
+    #[test]
+    fn parse_non_ascii() {
         let string = "80:FÁ:5B:41:10:6B";
         let address = string.parse::<MacAddress>().unwrap_err();
-        assert_eq!(MacParseError::InvalidLength, address);
+        assert_eq!(
+            MacParseError::NonAscii {
+                offset: 4,
+                found: 'Á'
+            },
+            address
+        );
+        assert_eq!(address.to_string(), "non-ASCII character 'Á' at offset 4");
     }
 
     #[test]
     fn parse_invalid_digit() {
         let string = "80:FA:ZZ:41:10:6B";
         let address = string.parse::<MacAddress>().unwrap_err();
-        assert_eq!(MacParseError::InvalidDigit, address);
+        assert_eq!(
+            MacParseError::UnexpectedChar {
+                offset: 6,
+                found: 'Z',
+                expected: ExpectedToken::HexDigit
+            },
+            address
+        );
+        assert_eq!(address.offset(), Some(6));
+        assert_eq!(
+            address.to_string(),
+            "unexpected character 'Z' at offset 6, expected a hex digit"
+        );
     }
 
     #[test]
     fn parse_invalid_separator() {
         let string = "80|FA|AA|41|10|6B";
         let address = string.parse::<MacAddress>().unwrap_err();
-        assert_eq!(MacParseError::InvalidDigit, address);
+        assert_eq!(
+            MacParseError::UnexpectedChar {
+                offset: 2,
+                found: '|',
+                expected: ExpectedToken::Separator
+            },
+            address
+        );
+    }
+
+    #[test]
+    fn display_padding() {
+        let address = MacAddress::new([0x80, 0xFA, 0x5B, 0x41, 0x10, 0x6B]);
+        assert_eq!(format!("{:>20}", address), "   80:FA:5B:41:10:6B");
+        assert_eq!(format!("{:<20}|", address), "80:FA:5B:41:10:6B   |");
+        assert_eq!(format!("{:*^21}", address), "**80:FA:5B:41:10:6B**");
+        assert_eq!(format!("{:5}", address), "80:FA:5B:41:10:6B");
+
+        let mut buf = [0u8; 17];
+        assert_eq!(address.to_str_buf(&mut buf), "80:FA:5B:41:10:6B");
     }
 
     #[cfg(feature = "serde")]
@@ -322,6 +531,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn convert() {
         for mac in MacAddressIterator::new().unwrap() {
@@ -0,0 +1,117 @@
+use crate::MacAddress;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+impl MacAddress {
+    /// Returns the Ethernet multicast address an IPv4 multicast group maps
+    /// onto, `01:00:5E` followed by the low 23 bits of the group address, as
+    /// described in [RFC 1112](https://www.rfc-editor.org/rfc/rfc1112#section-6.4).
+    ///
+    /// Returns `None` if `group` isn't in `224.0.0.0/4`.
+    pub fn from_ipv4_multicast(group: Ipv4Addr) -> Option<MacAddress> {
+        if !group.is_multicast() {
+            return None;
+        }
+
+        let o = group.octets();
+        Some(MacAddress::new([0x01, 0x00, 0x5E, o[1] & 0x7F, o[2], o[3]]))
+    }
+
+    /// Returns the Ethernet multicast address an IPv6 multicast group maps
+    /// onto, `33:33` followed by the low 32 bits of the group address, as
+    /// described in [RFC 2464](https://www.rfc-editor.org/rfc/rfc2464#section-7).
+    ///
+    /// Returns `None` if `group` isn't in `ff00::/8`.
+    pub fn from_ipv6_multicast(group: Ipv6Addr) -> Option<MacAddress> {
+        if !group.is_multicast() {
+            return None;
+        }
+
+        let o = group.octets();
+        Some(MacAddress::new([0x33, 0x33, o[12], o[13], o[14], o[15]]))
+    }
+
+    /// Returns the Ethernet multicast address of the solicited-node multicast
+    /// group (`ff02::1:ffXX:XXXX`) that neighbor discovery uses to resolve the
+    /// given unicast address.
+    pub fn from_ipv6_solicited_node(addr: Ipv6Addr) -> MacAddress {
+        let o = addr.octets();
+        MacAddress::new([0x33, 0x33, 0xFF, o[13], o[14], o[15]])
+    }
+
+    /// Returns the 32 IPv4 multicast groups that map onto this address, since
+    /// the mapping discards 5 bits of the group address.
+    ///
+    /// Returns `None` if this isn't an address in the IPv4 multicast range
+    /// `01:00:5E:00:00:00` to `01:00:5E:7F:FF:FF`.
+    pub fn ipv4_multicast_groups(self) -> Option<[Ipv4Addr; 32]> {
+        let b = self.bytes();
+
+        if b[..3] != [0x01, 0x00, 0x5E] || b[3] & 0x80 != 0 {
+            return None;
+        }
+
+        let mut groups = [Ipv4Addr::UNSPECIFIED; 32];
+        for (i, group) in groups.iter_mut().enumerate() {
+            let i = i as u8;
+            *group = Ipv4Addr::new(0xE0 | (i >> 1), ((i & 1) << 7) | b[3], b[4], b[5]);
+        }
+
+        Some(groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ipv4() {
+        let mac = MacAddress::from_ipv4_multicast(Ipv4Addr::new(239, 129, 2, 3)).unwrap();
+        assert_eq!(mac.bytes(), [0x01, 0x00, 0x5E, 0x01, 0x02, 0x03]);
+        assert_eq!(
+            MacAddress::from_ipv4_multicast(Ipv4Addr::new(224, 0, 0, 251)),
+            Some(MacAddress::new([0x01, 0x00, 0x5E, 0x00, 0x00, 0xFB]))
+        );
+        assert_eq!(
+            MacAddress::from_ipv4_multicast(Ipv4Addr::new(10, 0, 0, 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn ipv4_groups() {
+        let mac = MacAddress::new([0x01, 0x00, 0x5E, 0x01, 0x02, 0x03]);
+        let groups = mac.ipv4_multicast_groups().unwrap();
+        assert_eq!(groups[0], Ipv4Addr::new(224, 1, 2, 3));
+        assert_eq!(groups[1], Ipv4Addr::new(224, 129, 2, 3));
+        assert_eq!(groups[31], Ipv4Addr::new(239, 129, 2, 3));
+
+        for group in groups.iter() {
+            assert_eq!(MacAddress::from_ipv4_multicast(*group), Some(mac));
+        }
+
+        let unicast = MacAddress::new([0x00, 0x00, 0x5E, 0x01, 0x02, 0x03]);
+        assert_eq!(unicast.ipv4_multicast_groups(), None);
+        let high = MacAddress::new([0x01, 0x00, 0x5E, 0x80, 0x02, 0x03]);
+        assert_eq!(high.ipv4_multicast_groups(), None);
+    }
+
+    #[test]
+    fn ipv6() {
+        let all_nodes = "ff02::1".parse().unwrap();
+        assert_eq!(
+            MacAddress::from_ipv6_multicast(all_nodes),
+            Some(MacAddress::new([0x33, 0x33, 0x00, 0x00, 0x00, 0x01]))
+        );
+        assert_eq!(
+            MacAddress::from_ipv6_multicast("fe80::1".parse().unwrap()),
+            None
+        );
+
+        let unicast = "fe80::5054:ff:fe12:3456".parse().unwrap();
+        assert_eq!(
+            MacAddress::from_ipv6_solicited_node(unicast),
+            MacAddress::new([0x33, 0x33, 0xFF, 0x12, 0x34, 0x56])
+        );
+    }
+}
@@ -0,0 +1,570 @@
+use crate::{ExpectedToken, MacAddress, MacParseError};
+
+/// Options controlling which textual forms [`MacAddress::parse_with`]
+/// accepts.
+///
+/// [`ParseOptions::new`] accepts the same forms as the `FromStr`
+/// implementation: six groups of two hex digits separated by `:` or `-`
+/// (e.g. `00:1b:2c:3d:4e:5f`, even with the two mixed), or twelve hex digits
+/// without separators (e.g. `001b2c3d4e5f`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ParseOptions {
+    cisco: bool,
+    short_groups: bool,
+    trim: bool,
+    strict: bool,
+}
+
+impl ParseOptions {
+    /// Creates options accepting the same forms as `FromStr`.
+    pub fn new() -> ParseOptions {
+        ParseOptions::default()
+    }
+
+    /// Creates options accepting every supported form: Cisco dotted notation,
+    /// single-digit groups and surrounding whitespace.
+    pub fn lenient() -> ParseOptions {
+        ParseOptions::new()
+            .cisco(true)
+            .short_groups(true)
+            .trim_whitespace(true)
+    }
+
+    /// Sets whether to accept Cisco's dotted notation, three groups of four
+    /// hex digits separated by `.` (e.g. `001b.2c3d.4e5f`).
+    pub fn cisco(mut self, allow: bool) -> ParseOptions {
+        self.cisco = allow;
+        self
+    }
+
+    /// Sets whether to accept `:` or `-` separated groups of a single hex
+    /// digit, as printed by `arp` on BSD and macOS (e.g. `0:1b:2c:3:4:5`).
+    pub fn short_groups(mut self, allow: bool) -> ParseOptions {
+        self.short_groups = allow;
+        self
+    }
+
+    /// Sets whether to ignore leading and trailing whitespace.
+    pub fn trim_whitespace(mut self, trim: bool) -> ParseOptions {
+        self.trim = trim;
+        self
+    }
+
+    /// Sets whether every separator must be the same, rejecting e.g.
+    /// `00:1b-2c:3d:4e:5f`.
+    pub fn strict(mut self, strict: bool) -> ParseOptions {
+        self.strict = strict;
+        self
+    }
+}
+
+impl MacAddress {
+    /// Parses an address, accepting the forms enabled in `options`.
+    ///
+    /// `input.parse()` is equivalent to `MacAddress::parse_with(input,
+    /// ParseOptions::new())`.
+    pub fn parse_with(input: &str, options: ParseOptions) -> Result<MacAddress, MacParseError> {
+        let mut bytes = [0u8; 6];
+        parse_hex_groups(input, &mut bytes, options)?;
+
+        Ok(MacAddress::new(bytes))
+    }
+
+    /// Parses an address from ASCII bytes, such as a field of a packet or log
+    /// buffer, without first checking that they're valid UTF-8. Accepts the
+    /// same forms as `FromStr`.
+    pub fn parse_ascii(input: &[u8]) -> Result<MacAddress, MacParseError> {
+        let mut bytes = [0u8; 6];
+        parse_bytes(input, &mut bytes, ParseOptions::new())?;
+
+        Ok(MacAddress::new(bytes))
+    }
+
+    /// Parses an address at the start of `input`, returning it along with the
+    /// rest of `input` following it, for use in larger parsers. Accepts the
+    /// same forms as `FromStr`, choosing between them by whether the third
+    /// byte is a separator.
+    ///
+    /// Nothing is checked about what follows the address, so e.g.
+    /// `00:1b:2c:3d:4e:5f6` parses as `00:1B:2C:3D:4E:5F` followed by `6`.
+    pub fn parse_ascii_prefix(input: &[u8]) -> Result<(MacAddress, &[u8]), MacParseError> {
+        let mut bytes = [0u8; 6];
+        let len = parse_leading(input, &mut bytes, ParseOptions::new())?;
+
+        Ok((MacAddress::new(bytes), &input[len..]))
+    }
+
+    /// Parses an address in a `const` context, accepting the same forms as
+    /// `FromStr`. See also the [`mac!`](crate::mac) macro, which rejects
+    /// invalid addresses at compile time.
+    pub const fn parse_const(input: &str) -> Result<MacAddress, MacParseError> {
+        let s = input.as_bytes();
+
+        let mut i = 0;
+        while i < s.len() {
+            if !s[i].is_ascii() {
+                return Err(MacParseError::NonAscii {
+                    offset: i,
+                    found: decode_char(s, i),
+                });
+            }
+            i += 1;
+        }
+
+        let step = match s.len() {
+            12 => 2,
+            17 => 3,
+            _ => return Err(MacParseError::InvalidLength),
+        };
+
+        let mut bytes = [0u8; 6];
+        let mut g = 0;
+        while g < bytes.len() {
+            let start = g * step;
+            if step == 3 && g != 0 {
+                if let Err(e) = check_separator(s, start - 1, b":-", None) {
+                    return Err(e);
+                }
+            }
+
+            let hi = match digit(s, start) {
+                Ok(d) => d,
+                Err(e) => return Err(e),
+            };
+            let lo = match digit(s, start + 1) {
+                Ok(d) => d,
+                Err(e) => return Err(e),
+            };
+            bytes[g] = hi << 4 | lo;
+            g += 1;
+        }
+
+        Ok(MacAddress::new(bytes))
+    }
+}
+
+/// Creates a [`MacAddress`] from a string literal, failing to compile if it
+/// isn't a valid address. Accepts the same forms as `FromStr`.
+///
+/// ```
+/// use mac_address::{mac, MacAddress};
+///
+/// const GATEWAY: MacAddress = mac!("00:1b:2c:3d:4e:5f");
+/// assert_eq!(GATEWAY, "00:1B:2C:3D:4E:5F".parse().unwrap());
+/// ```
+///
+/// ```compile_fail
+/// use mac_address::{mac, MacAddress};
+///
+/// const GATEWAY: MacAddress = mac!("00:1b:2c:3d:4e:5g");
+/// ```
+#[macro_export]
+macro_rules! mac {
+    ($s:literal) => {{
+        const MAC: $crate::MacAddress = match $crate::MacAddress::parse_const($s) {
+            Ok(mac) => mac,
+            Err(_) => panic!(concat!("invalid MAC address: ", $s)),
+        };
+        MAC
+    }};
+}
+
+/// Parses `input` into `out.len()` bytes, accepting the forms enabled in
+/// `options`.
+pub(crate) fn parse_hex_groups(
+    input: &str,
+    out: &mut [u8],
+    options: ParseOptions,
+) -> Result<(), MacParseError> {
+    let (input, start) = if options.trim {
+        let trimmed = input.trim_start();
+        (trimmed.trim_end(), input.len() - trimmed.len())
+    } else {
+        (input, 0)
+    };
+
+    parse_bytes(input.as_bytes(), out, options).map_err(|e| e.offset_by(start))
+}
+
+/// Parses all of `s` into `out.len()` bytes, accepting the forms enabled in
+/// `options` other than surrounding whitespace.
+pub(crate) fn parse_bytes(
+    s: &[u8],
+    out: &mut [u8],
+    options: ParseOptions,
+) -> Result<(), MacParseError> {
+    // report non-ASCII input as such even if the length is also wrong
+    if let Some(offset) = s.iter().position(|b| !b.is_ascii()) {
+        return Err(MacParseError::NonAscii {
+            offset,
+            found: decode_char(s, offset),
+        });
+    }
+
+    let is_separator = |c: &u8| *c == b':' || *c == b'-';
+
+    let len = if options.cisco && s.contains(&b'.') {
+        // dotted groups of two bytes, e.g. 001b.2c3d.4e5f
+        parse_fixed(s, out, 2, b".", false)?
+    } else if options.short_groups && s.iter().any(is_separator) {
+        // groups of one or two digits, e.g. 0:1b:2c:3:4:5
+        parse_short(s, out, options.strict)?
+    } else if s.len() == out.len() * 2 {
+        // address without separators, e.g. 001b2c3d4e5f
+        parse_fixed(s, out, 1, b"", false)?
+    } else {
+        // address with separators, e.g. 00:1b:2c:3d:4e:5f
+        parse_fixed(s, out, 1, b":-", options.strict)?
+    };
+
+    if len != s.len() {
+        return Err(MacParseError::InvalidLength);
+    }
+
+    Ok(())
+}
+
+/// Parses an address at the start of `s` into `out.len()` bytes, accepting
+/// the forms enabled in `options` other than surrounding whitespace, and
+/// returns the number of bytes it took up.
+///
+/// The form is chosen by the first separator, so e.g. `001b2c3d4e5f:01`
+/// parses as an address without separators followed by `:01`.
+pub(crate) fn parse_leading(
+    s: &[u8],
+    out: &mut [u8],
+    options: ParseOptions,
+) -> Result<usize, MacParseError> {
+    let is_separator = |c: Option<&u8>| c == Some(&b':') || c == Some(&b'-');
+
+    if options.cisco && s.get(4) == Some(&b'.') {
+        parse_fixed(s, out, 2, b".", false)
+    } else if options.short_groups && (is_separator(s.get(1)) || is_separator(s.get(2))) {
+        parse_short(s, out, options.strict)
+    } else if is_separator(s.get(2)) {
+        parse_fixed(s, out, 1, b":-", options.strict)
+    } else {
+        parse_fixed(s, out, 1, b"", false)
+    }
+}
+
+/// Parses groups of `group_bytes` bytes, each written as exactly two hex
+/// digits per byte, separated by one of `separators` if there are any, and
+/// returns the number of bytes of `s` they took up.
+fn parse_fixed(
+    s: &[u8],
+    out: &mut [u8],
+    group_bytes: usize,
+    separators: &[u8],
+    strict: bool,
+) -> Result<usize, MacParseError> {
+    let width = group_bytes * 2;
+    let step = width + usize::from(!separators.is_empty());
+    let groups = out.len() / group_bytes;
+    let len = groups * step - (step - width);
+
+    if s.len() < len {
+        return Err(MacParseError::InvalidLength);
+    }
+
+    for (g, chunk) in out.chunks_mut(group_bytes).enumerate() {
+        let start = g * step;
+        if g != 0 && !separators.is_empty() {
+            check_separator(s, start - 1, separators, strict.then(|| s[width]))?;
+        }
+
+        for (i, b) in chunk.iter_mut().enumerate() {
+            *b = digit(s, start + i * 2)? << 4 | digit(s, start + i * 2 + 1)?;
+        }
+    }
+
+    Ok(len)
+}
+
+/// Parses `:` or `-` separated groups of one or two hex digits, and returns
+/// the number of bytes of `s` they took up.
+fn parse_short(s: &[u8], out: &mut [u8], strict: bool) -> Result<usize, MacParseError> {
+    let mut pos = 0;
+    let mut first_sep = None;
+
+    for (i, b) in out.iter_mut().enumerate() {
+        if i != 0 {
+            if pos == s.len() {
+                return Err(MacParseError::InvalidLength);
+            }
+
+            check_separator(s, pos, b":-", first_sep.filter(|_| strict))?;
+            first_sep.get_or_insert(s[pos]);
+            pos += 1;
+        }
+
+        if pos == s.len() {
+            return Err(MacParseError::InvalidLength);
+        }
+
+        *b = digit(s, pos)?;
+        pos += 1;
+
+        if let Some(lo) = s.get(pos).copied().and_then(hex_value) {
+            *b = *b << 4 | lo;
+            pos += 1;
+        }
+    }
+
+    Ok(pos)
+}
+
+/// Checks that `s[offset]` is one of `separators`, and the same as `first` if
+/// given.
+pub(crate) const fn check_separator(
+    s: &[u8],
+    offset: usize,
+    separators: &[u8],
+    first: Option<u8>,
+) -> Result<(), MacParseError> {
+    let found = s[offset];
+    if !found.is_ascii() {
+        return Err(MacParseError::NonAscii {
+            offset,
+            found: decode_char(s, offset),
+        });
+    }
+
+    let mut i = 0;
+    while i < separators.len() && separators[i] != found {
+        i += 1;
+    }
+
+    if i == separators.len() {
+        return Err(MacParseError::UnexpectedChar {
+            offset,
+            found: found as char,
+            expected: ExpectedToken::Separator,
+        });
+    }
+
+    match first {
+        Some(expected) if expected != found => Err(MacParseError::MixedSeparators {
+            offset,
+            found: found as char,
+            expected: expected as char,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the value of the hex digit at `s[offset]`.
+pub(crate) const fn digit(s: &[u8], offset: usize) -> Result<u8, MacParseError> {
+    match hex_value(s[offset]) {
+        Some(d) => Ok(d),
+        None if !s[offset].is_ascii() => Err(MacParseError::NonAscii {
+            offset,
+            found: decode_char(s, offset),
+        }),
+        None => Err(MacParseError::UnexpectedChar {
+            offset,
+            found: s[offset] as char,
+            expected: ExpectedToken::HexDigit,
+        }),
+    }
+}
+
+const fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decodes the UTF-8 character starting at `s[offset]`, for reporting
+/// non-ASCII input from `const` code. Invalid UTF-8 gives
+/// [`char::REPLACEMENT_CHARACTER`].
+const fn decode_char(s: &[u8], offset: usize) -> char {
+    let b = s[offset];
+    let (len, mut c) = match b {
+        0xC0..=0xDF => (2, (b & 0x1F) as u32),
+        0xE0..=0xEF => (3, (b & 0x0F) as u32),
+        0xF0..=0xF7 => (4, (b & 0x07) as u32),
+        _ => return char::REPLACEMENT_CHARACTER,
+    };
+
+    let mut i = 1;
+    while i < len {
+        if offset + i >= s.len() || s[offset + i] & 0xC0 != 0x80 {
+            return char::REPLACEMENT_CHARACTER;
+        }
+
+        c = c << 6 | (s[offset + i] & 0x3F) as u32;
+        i += 1;
+    }
+
+    match char::from_u32(c) {
+        Some(c) => c,
+        None => char::REPLACEMENT_CHARACTER,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_mac;
+
+    fn unexpected(offset: usize, found: char, expected: ExpectedToken) -> MacParseError {
+        MacParseError::UnexpectedChar {
+            offset,
+            found,
+            expected,
+        }
+    }
+
+    #[test]
+    fn default_matches_from_str() {
+        let parse = |s| MacAddress::parse_with(s, ParseOptions::new());
+        let expected = test_mac("00:1B:2C:3D:4E:5F");
+
+        assert_eq!(parse("00:1b:2c:3d:4e:5f"), Ok(expected));
+        assert_eq!(parse("00-1b:2c-3d:4e-5f"), Ok(expected));
+        assert_eq!(parse("001b2c3d4e5f"), Ok(expected));
+        assert_eq!(parse("001b.2c3d.4e5f"), Err(MacParseError::InvalidLength));
+        assert_eq!(parse("0:1b:2c:3:4:5"), Err(MacParseError::InvalidLength));
+        assert_eq!(parse(" 001b2c3d4e5f"), Err(MacParseError::InvalidLength));
+        assert_eq!(
+            parse("00:1b:2c:3d:4e:+5"),
+            Err(unexpected(15, '+', ExpectedToken::HexDigit))
+        );
+    }
+
+    #[test]
+    fn lenient() {
+        let parse = |s| MacAddress::parse_with(s, ParseOptions::lenient());
+        let expected = test_mac("00:1B:2C:03:04:05");
+
+        assert_eq!(parse("001B.2C03.0405"), Ok(expected));
+        assert_eq!(parse("0:1b:2c:3:4:5"), Ok(expected));
+        assert_eq!(parse("0-1b-2c-3-4-5"), Ok(expected));
+        assert_eq!(parse(" \t00:1b:2c:03:04:05\n"), Ok(expected));
+        assert_eq!(parse("001b2c030405"), Ok(expected));
+
+        assert_eq!(parse("001b.2c03.04"), Err(MacParseError::InvalidLength));
+        assert_eq!(
+            parse("001b.2c03:0405"),
+            Err(unexpected(9, ':', ExpectedToken::Separator))
+        );
+        assert_eq!(parse("0:1b:2c:3:4"), Err(MacParseError::InvalidLength));
+        assert_eq!(parse("0:1b:2c:3:4:5:6"), Err(MacParseError::InvalidLength));
+        assert_eq!(parse("0:1b:2c:3:4:555"), Err(MacParseError::InvalidLength));
+        assert_eq!(
+            parse("0:1b::3:4:5"),
+            Err(unexpected(5, ':', ExpectedToken::HexDigit))
+        );
+        assert_eq!(
+            parse("  0:1b:2g:3:4:5"),
+            Err(unexpected(8, 'g', ExpectedToken::Separator))
+        );
+        assert_eq!(
+            parse(" 0:1b:2c:3:4:é"),
+            Err(MacParseError::NonAscii {
+                offset: 13,
+                found: 'é'
+            })
+        );
+    }
+
+    #[test]
+    fn strict() {
+        let options = ParseOptions::lenient().strict(true);
+        let parse = |s| MacAddress::parse_with(s, options);
+
+        assert!(parse("00-1b-2c-3d-4e-5f").is_ok());
+        assert!(parse("0:1b:2c:3:4:5").is_ok());
+        assert_eq!(
+            parse("00-1b:2c-3d:4e-5f"),
+            Err(MacParseError::MixedSeparators {
+                offset: 5,
+                found: ':',
+                expected: '-'
+            })
+        );
+        assert_eq!(
+            parse("0:1b:2c:3:4-5").unwrap_err().to_string(),
+            "mixed separators, found '-' at offset 11 after ':'"
+        );
+    }
+
+    #[test]
+    fn parse_const() {
+        const GATEWAY: MacAddress = mac!("00:1b:2c:3d:4e:5f");
+        assert_eq!(GATEWAY, test_mac("00:1B:2C:3D:4E:5F"));
+        assert_eq!(mac!("001B2C3D4E5F"), GATEWAY);
+
+        for input in &[
+            "00:1b:2c:3d:4e:5f",
+            "00-1b:2c-3d:4e-5f",
+            "001b2c3d4e5f",
+            "00:1b:2c:3d:4e",
+            "00:1b:2c:3d:4e:5g",
+            "00|1b|2c|3d|4e|5f",
+            "00:1b:2c:3d:4e:é",
+            "00:1b:2c:3d:4e:+5",
+        ] {
+            assert_eq!(MacAddress::parse_const(input), input.parse());
+        }
+    }
+
+    #[test]
+    fn ascii() {
+        let expected = test_mac("00:1B:2C:3D:4E:5F");
+
+        assert_eq!(MacAddress::parse_ascii(b"00:1b:2c:3d:4e:5f"), Ok(expected));
+        assert_eq!(MacAddress::parse_ascii(b"001B2C3D4E5F"), Ok(expected));
+        assert_eq!(
+            MacAddress::parse_ascii(b"00:1b:2c:3d:4e:5f "),
+            Err(MacParseError::InvalidLength)
+        );
+        assert_eq!(
+            MacAddress::parse_ascii(b"00:1b:2c:3d:4e:\xff"),
+            Err(MacParseError::NonAscii {
+                offset: 15,
+                found: char::REPLACEMENT_CHARACTER
+            })
+        );
+    }
+
+    #[test]
+    fn ascii_prefix() {
+        let expected = test_mac("00:1B:2C:3D:4E:5F");
+
+        assert_eq!(
+            MacAddress::parse_ascii_prefix(b"00:1b:2c:3d:4e:5f via eth0"),
+            Ok((expected, &b" via eth0"[..]))
+        );
+        assert_eq!(
+            MacAddress::parse_ascii_prefix(b"001b2c3d4e5f,1500"),
+            Ok((expected, &b",1500"[..]))
+        );
+        assert_eq!(
+            MacAddress::parse_ascii_prefix(b"00-1b-2c-3d-4e-5f"),
+            Ok((expected, &b""[..]))
+        );
+        assert_eq!(
+            MacAddress::parse_ascii_prefix(b"00:1b:2c:3d:4e"),
+            Err(MacParseError::InvalidLength)
+        );
+        assert_eq!(
+            MacAddress::parse_ascii_prefix(b"00:1b:2c:3d:4e:5g"),
+            Err(MacParseError::UnexpectedChar {
+                offset: 16,
+                found: 'g',
+                expected: ExpectedToken::HexDigit
+            })
+        );
+
+        let mut out = [0u8; 6];
+        let lenient = ParseOptions::lenient();
+        assert_eq!(parse_leading(b"0:1b:2c:3:4:5 ", &mut out, lenient), Ok(13));
+        assert_eq!(parse_leading(b"001b.2c3d.4e5f.", &mut out, lenient), Ok(14));
+        assert_eq!(MacAddress::new(out), expected);
+    }
+}
@@ -0,0 +1,355 @@
+use crate::parse::{check_separator, digit};
+use crate::{MacAddress, MacParseError, MacPrefix};
+use core::convert::TryFrom;
+
+/// A value/mask pattern that matches MAC addresses, as used in switch and
+/// packet filter configurations.
+///
+/// Patterns can be parsed from a wildcard form, where a group of `*` matches
+/// any byte and a `?` matches any nibble (e.g. `00:11:22:*:*:*` or
+/// `00:11:22:3?:*:*`), or from an address and a mask separated by whitespace
+/// or `/`, as accepted by tcpdump and ebtables (e.g.
+/// `00:11:22:00:00:00/FF:FF:FF:00:00:00`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "alloc::borrow::Cow<'_, str>"))]
+pub struct MacPattern {
+    value: MacAddress,
+    mask: MacAddress,
+}
+
+impl MacPattern {
+    /// Creates a pattern matching addresses that equal `value` in every bit
+    /// set in `mask`. Bits of `value` outside of `mask` are cleared.
+    pub fn new(value: MacAddress, mask: MacAddress) -> MacPattern {
+        let (v, m) = (value.bytes(), mask.bytes());
+        let mut bytes = [0u8; 6];
+        for (b, (v, m)) in bytes.iter_mut().zip(v.iter().zip(m.iter())) {
+            *b = v & m;
+        }
+
+        MacPattern {
+            value: MacAddress::new(bytes),
+            mask,
+        }
+    }
+
+    /// Returns the value the masked bits must have.
+    pub fn value(self) -> MacAddress {
+        self.value
+    }
+
+    /// Returns the mask of the bits that must match.
+    pub fn mask(self) -> MacAddress {
+        self.mask
+    }
+
+    /// Returns `true` if `mac` matches the pattern.
+    pub fn matches(&self, mac: &MacAddress) -> bool {
+        let (v, m) = (self.value.bytes(), self.mask.bytes());
+
+        mac.bytes()
+            .iter()
+            .zip(v.iter().zip(m.iter()))
+            .all(|(b, (v, m))| b & m == *v)
+    }
+
+    /// Parses the wildcard form, where each of the six groups is either `*`
+    /// or two characters that are hex digits or `?`.
+    fn parse_wildcard(input: &str) -> Result<MacPattern, MacParseError> {
+        let s = input.as_bytes();
+        if let Some(offset) = input.find(|c: char| !c.is_ascii()) {
+            let found = input[offset..].chars().next().unwrap_or_default();
+            return Err(MacParseError::NonAscii { offset, found });
+        }
+
+        let mut value = [0u8; 6];
+        let mut mask = [0u8; 6];
+        let mut pos = 0;
+
+        for (i, (v, m)) in value.iter_mut().zip(mask.iter_mut()).enumerate() {
+            if i != 0 {
+                if pos == s.len() {
+                    return Err(MacParseError::InvalidLength);
+                }
+
+                check_separator(s, pos, b":-", None)?;
+                pos += 1;
+            }
+
+            if s.get(pos) == Some(&b'*') {
+                pos += 1;
+                continue;
+            }
+
+            for _ in 0..2 {
+                if pos == s.len() {
+                    return Err(MacParseError::InvalidLength);
+                }
+
+                *v <<= 4;
+                *m <<= 4;
+
+                if s[pos] != b'?' {
+                    *v |= digit(s, pos)?;
+                    *m |= 0x0F;
+                }
+                pos += 1;
+            }
+        }
+
+        if pos != s.len() {
+            check_separator(s, pos, b":-", None)?;
+            return Err(MacParseError::InvalidLength);
+        }
+
+        Ok(MacPattern::new(
+            MacAddress::new(value),
+            MacAddress::new(mask),
+        ))
+    }
+}
+
+impl From<MacAddress> for MacPattern {
+    fn from(mac: MacAddress) -> Self {
+        MacPattern::new(mac, MacAddress::BROADCAST)
+    }
+}
+
+impl From<MacPrefix> for MacPattern {
+    fn from(prefix: MacPrefix) -> Self {
+        MacPattern::new(prefix.network(), prefix.mask())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MacPattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl core::str::FromStr for MacPattern {
+    type Err = MacParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let trimmed = input.trim_start();
+        let start = input.len() - trimmed.len();
+        let input = trimmed.trim_end();
+
+        if let Some(i) = input.find(|c: char| c == '/' || c.is_whitespace()) {
+            let value = input[..i]
+                .parse()
+                .map_err(|e: MacParseError| e.offset_by(start))?;
+            let mask = input[i + 1..].trim_start();
+            let mask_start = start + input.len() - mask.len();
+            let mask = mask
+                .parse()
+                .map_err(|e: MacParseError| e.offset_by(mask_start))?;
+            return Ok(MacPattern::new(value, mask));
+        }
+
+        if input.contains(['*', '?']) {
+            MacPattern::parse_wildcard(input).map_err(|e| e.offset_by(start))
+        } else {
+            input
+                .parse::<MacAddress>()
+                .map(MacPattern::from)
+                .map_err(|e| e.offset_by(start))
+        }
+    }
+}
+
+impl TryFrom<&'_ str> for MacPattern {
+    type Error = MacParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<alloc::borrow::Cow<'_, str>> for MacPattern {
+    type Error = MacParseError;
+
+    fn try_from(value: alloc::borrow::Cow<'_, str>) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Patterns whose mask covers whole nibbles are displayed in the wildcard
+/// form, others as `value/mask`.
+impl core::fmt::Display for MacPattern {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let (v, m) = (self.value.bytes(), self.mask.bytes());
+        let nibbles = |b: u8| [b >> 4, b & 0x0F];
+        let mut buf = [0u8; 35];
+
+        if !m
+            .iter()
+            .flat_map(|&b| nibbles(b))
+            .all(|n| n == 0 || n == 0x0F)
+        {
+            let mut mac = [0u8; 17];
+            buf[..17].copy_from_slice(self.value.to_str_buf(&mut mac).as_bytes());
+            buf[17] = b'/';
+            buf[18..].copy_from_slice(self.mask.to_str_buf(&mut mac).as_bytes());
+
+            // only ASCII was written
+            return f.pad(core::str::from_utf8(&buf).unwrap_or_default());
+        }
+
+        let mut len = 0;
+        for (i, (v, m)) in v.iter().zip(m.iter()).enumerate() {
+            if i != 0 {
+                buf[len] = b':';
+                len += 1;
+            }
+
+            if *m == 0 {
+                buf[len] = b'*';
+                len += 1;
+                continue;
+            }
+
+            for (v, m) in nibbles(*v).iter().zip(nibbles(*m).iter()) {
+                buf[len] = if *m == 0 {
+                    b'?'
+                } else {
+                    b"0123456789ABCDEF"[usize::from(*v)]
+                };
+                len += 1;
+            }
+        }
+
+        // only ASCII was written
+        f.pad(core::str::from_utf8(&buf[..len]).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_mac, ExpectedToken};
+
+    #[test]
+    fn wildcards() {
+        let pattern = "00:11:22:*:*:*".parse::<MacPattern>().unwrap();
+        assert!(pattern.matches(&test_mac("00:11:22:33:44:55")));
+        assert!(!pattern.matches(&test_mac("00:11:23:33:44:55")));
+        assert_eq!(pattern.mask(), test_mac("FF:FF:FF:00:00:00"));
+
+        let pattern = "*:*:*:*:*:01".parse::<MacPattern>().unwrap();
+        assert!(pattern.matches(&test_mac("AA:BB:CC:DD:EE:01")));
+        assert!(!pattern.matches(&test_mac("AA:BB:CC:DD:EE:02")));
+
+        let pattern = "00-11-22-3?-*-*".parse::<MacPattern>().unwrap();
+        assert!(pattern.matches(&test_mac("00:11:22:3F:44:55")));
+        assert!(!pattern.matches(&test_mac("00:11:22:4F:44:55")));
+        assert_eq!(pattern.to_string(), "00:11:22:3?:*:*");
+        assert_eq!(format!("{:>17}", pattern), "  00:11:22:3?:*:*");
+    }
+
+    #[test]
+    fn value_mask() {
+        let expected = "00:11:22:*:*:*".parse::<MacPattern>().unwrap();
+        assert_eq!(
+            "00:11:22:00:00:00 ff:ff:ff:00:00:00".parse::<MacPattern>(),
+            Ok(expected)
+        );
+        assert_eq!(
+            "00:11:22:33:44:55/FF:FF:FF:00:00:00".parse::<MacPattern>(),
+            Ok(expected)
+        );
+
+        let odd = "01:00:00:00:00:00/01:00:00:00:00:00"
+            .parse::<MacPattern>()
+            .unwrap();
+        assert!(odd.matches(&test_mac("33:33:00:00:00:01")));
+        assert!(!odd.matches(&test_mac("00:11:22:33:44:55")));
+        assert_eq!(odd.to_string(), "01:00:00:00:00:00/01:00:00:00:00:00");
+        assert_eq!(
+            format!("{:^37}", odd),
+            " 01:00:00:00:00:00/01:00:00:00:00:00 "
+        );
+    }
+
+    #[test]
+    fn exact_and_prefix() {
+        let pattern = "00:11:22:33:44:55".parse::<MacPattern>().unwrap();
+        assert!(pattern.matches(&test_mac("00:11:22:33:44:55")));
+        assert!(!pattern.matches(&test_mac("00:11:22:33:44:56")));
+        assert_eq!(pattern.to_string(), "00:11:22:33:44:55");
+
+        let prefix = "00:11:22:33:40:00/36".parse::<MacPrefix>().unwrap();
+        assert_eq!(MacPattern::from(prefix).to_string(), "00:11:22:33:4?:*");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            "00:11:22:*:*".parse::<MacPattern>(),
+            Err(MacParseError::InvalidLength)
+        );
+        assert_eq!(
+            "00:11:22:*:*:*:*".parse::<MacPattern>(),
+            Err(MacParseError::InvalidLength)
+        );
+        assert_eq!(
+            " 00:11:ZZ:33:44:55".parse::<MacPattern>(),
+            Err(MacParseError::UnexpectedChar {
+                offset: 7,
+                found: 'Z',
+                expected: ExpectedToken::HexDigit
+            })
+        );
+        assert_eq!(
+            "00:11:2G:*:*:*".parse::<MacPattern>(),
+            Err(MacParseError::UnexpectedChar {
+                offset: 7,
+                found: 'G',
+                expected: ExpectedToken::HexDigit
+            })
+        );
+        assert_eq!(
+            "00:11:2G:*:*:*".parse::<MacPattern>().unwrap_err().offset(),
+            "00:11:2G:00:00:00"
+                .parse::<MacPattern>()
+                .unwrap_err()
+                .offset()
+        );
+        assert_eq!(
+            " 00:11:22:3*:*:*".parse::<MacPattern>(),
+            Err(MacParseError::UnexpectedChar {
+                offset: 11,
+                found: '*',
+                expected: ExpectedToken::HexDigit
+            })
+        );
+        assert_eq!(
+            "00:11:22:*3:*:*".parse::<MacPattern>(),
+            Err(MacParseError::UnexpectedChar {
+                offset: 10,
+                found: '3',
+                expected: ExpectedToken::Separator
+            })
+        );
+        assert_eq!(
+            "00:11:22:*:*:é".parse::<MacPattern>(),
+            Err(MacParseError::NonAscii {
+                offset: 13,
+                found: 'é'
+            })
+        );
+        assert_eq!(
+            " 00:11:22:00:00:00/FF:FF:FF:0O:00:00"
+                .parse::<MacPattern>()
+                .unwrap_err()
+                .offset(),
+            Some(29)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{MacAddress, MacAddressRange, MacPrefix};
+#[cfg(feature = "std")]
+use crate::{MacAddressError, MacAddressIterator};
+use alloc::collections::BTreeSet;
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+
+/// Hands out unique MAC addresses from a range, e.g. for provisioning VMs and
+/// containers.
+///
+/// Addresses are allocated in order starting after the most recently
+/// allocated one, wrapping around at the end of the range, so a released
+/// address isn't handed out again until the rest of the range is in use.
+/// With the `serde` feature the pool can be serialized to persist its
+/// allocations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "MacPoolState"))]
+pub struct MacPool {
+    first: MacAddress,
+    last: MacAddress,
+    next: MacAddress,
+    empty: bool,
+    allocated: BTreeSet<MacAddress>,
+    reserved: BTreeSet<MacAddress>,
+}
+
+impl MacPool {
+    /// Creates a pool of the addresses in `range`. If the range is empty, so
+    /// is the pool, and it never allocates anything.
+    pub fn new(range: MacAddressRange) -> MacPool {
+        MacPool {
+            first: range.start(),
+            last: range.end(),
+            next: range.start(),
+            empty: range.is_empty(),
+            allocated: BTreeSet::new(),
+            reserved: BTreeSet::new(),
+        }
+    }
+
+    /// Creates a pool of the addresses in `prefix`.
+    pub fn from_prefix(prefix: MacPrefix) -> MacPool {
+        MacPool::new(prefix.addresses())
+    }
+
+    /// Returns `true` if `mac` is in the range this pool allocates from.
+    pub fn contains(&self, mac: &MacAddress) -> bool {
+        !self.empty && self.first <= *mac && *mac <= self.last
+    }
+
+    /// Prevents `mac` from ever being allocated, e.g. because it's statically
+    /// assigned elsewhere. Returns `false` if it was already reserved.
+    pub fn reserve(&mut self, mac: MacAddress) -> bool {
+        self.reserved.insert(mac)
+    }
+
+    /// Removes a reservation made with [`MacPool::reserve`]. Returns `false`
+    /// if `mac` wasn't reserved.
+    pub fn unreserve(&mut self, mac: &MacAddress) -> bool {
+        self.reserved.remove(mac)
+    }
+
+    /// Reserves every MAC address currently present on the host, as listed by
+    /// [`MacAddressIterator`].
+    #[cfg(feature = "std")]
+    pub fn reserve_host_addresses(&mut self) -> Result<(), MacAddressError> {
+        self.reserved.extend(MacAddressIterator::new()?);
+        Ok(())
+    }
+
+    /// Allocates the next free address, or returns `None` if every address in
+    /// the range is allocated or reserved.
+    pub fn allocate(&mut self) -> Option<MacAddress> {
+        if self.empty {
+            return None;
+        }
+
+        let next = self.next;
+        let tail = MacAddressRange::new(next, self.last);
+        let head = MacAddressRange::new(self.first, next).take_while(|mac| *mac != next);
+
+        let mac = tail.chain(head).find(|mac| self.is_free(mac))?;
+        self.allocated.insert(mac);
+        self.next = mac.next().filter(|m| *m <= self.last).unwrap_or(self.first);
+
+        Some(mac)
+    }
+
+    /// Marks a specific address as allocated, e.g. when restoring existing
+    /// guests. Returns `false` if it's outside the range, reserved or already
+    /// allocated.
+    pub fn allocate_specific(&mut self, mac: MacAddress) -> bool {
+        if !self.contains(&mac) || !self.is_free(&mac) {
+            return false;
+        }
+
+        self.allocated.insert(mac)
+    }
+
+    /// Returns an allocated address to the pool so it can be reused. Returns
+    /// `false` if it wasn't allocated.
+    pub fn release(&mut self, mac: &MacAddress) -> bool {
+        self.allocated.remove(mac)
+    }
+
+    /// Returns `true` if `mac` is currently allocated.
+    pub fn is_allocated(&self, mac: &MacAddress) -> bool {
+        self.allocated.contains(mac)
+    }
+
+    /// Returns an iterator over the allocated addresses, in order.
+    pub fn allocated(&self) -> impl Iterator<Item = MacAddress> + '_ {
+        self.allocated.iter().copied()
+    }
+
+    fn is_free(&self, mac: &MacAddress) -> bool {
+        !self.allocated.contains(mac) && !self.reserved.contains(mac)
+    }
+}
+
+impl From<MacPrefix> for MacPool {
+    fn from(prefix: MacPrefix) -> Self {
+        MacPool::from_prefix(prefix)
+    }
+}
+
+/// The serialized form of a [`MacPool`], checked before it's restored so a
+/// tampered state can't allocate outside the range.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct MacPoolState {
+    first: MacAddress,
+    last: MacAddress,
+    next: MacAddress,
+    empty: bool,
+    allocated: BTreeSet<MacAddress>,
+    reserved: BTreeSet<MacAddress>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MacPoolState> for MacPool {
+    type Error = &'static str;
+
+    fn try_from(state: MacPoolState) -> Result<Self, Self::Error> {
+        let pool = MacPool {
+            first: state.first,
+            last: state.last,
+            next: state.next,
+            empty: state.empty,
+            allocated: state.allocated,
+            reserved: state.reserved,
+        };
+
+        if !pool.empty && !pool.contains(&pool.next) {
+            return Err("next address is outside the pool's range");
+        }
+        if !pool.allocated.iter().all(|mac| pool.contains(mac)) {
+            return Err("allocated address is outside the pool's range");
+        }
+
+        Ok(pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_mac;
+
+    #[test]
+    fn allocate_and_release() {
+        let mut pool = MacPool::from_prefix("52:54:00:00:00:00/46".parse().unwrap());
+        assert!(pool.reserve(test_mac("52:54:00:00:00:01")));
+
+        assert_eq!(pool.allocate(), Some(test_mac("52:54:00:00:00:00")));
+        assert_eq!(pool.allocate(), Some(test_mac("52:54:00:00:00:02")));
+        assert_eq!(pool.allocate(), Some(test_mac("52:54:00:00:00:03")));
+        assert_eq!(pool.allocate(), None);
+
+        assert!(pool.release(&test_mac("52:54:00:00:00:02")));
+        assert!(!pool.release(&test_mac("52:54:00:00:00:02")));
+        assert_eq!(pool.allocate(), Some(test_mac("52:54:00:00:00:02")));
+
+        assert!(pool.unreserve(&test_mac("52:54:00:00:00:01")));
+        assert_eq!(pool.allocate(), Some(test_mac("52:54:00:00:00:01")));
+        assert_eq!(pool.allocated().count(), 4);
+    }
+
+    #[test]
+    fn released_addresses_are_reused_last() {
+        let mut pool = MacPool::from_prefix("52:54:00:00:00:00/44".parse().unwrap());
+        let a = pool.allocate().unwrap();
+        let b = pool.allocate().unwrap();
+        pool.release(&a);
+
+        let c = pool.allocate().unwrap();
+        assert_ne!(c, a);
+        assert_eq!(c, b.next().unwrap());
+    }
+
+    #[test]
+    fn specific() {
+        let mut pool = MacPool::from_prefix("52:54:00:00:00:00/46".parse().unwrap());
+        assert!(pool.allocate_specific(test_mac("52:54:00:00:00:00")));
+        assert!(!pool.allocate_specific(test_mac("52:54:00:00:00:00")));
+        assert!(!pool.allocate_specific(test_mac("52:54:00:00:00:04")));
+        assert_eq!(pool.allocate(), Some(test_mac("52:54:00:00:00:01")));
+    }
+
+    #[test]
+    fn empty_range() {
+        let range =
+            MacAddressRange::new(test_mac("00:00:00:00:00:10"), test_mac("00:00:00:00:00:05"));
+        let mut pool = MacPool::new(range);
+        assert_eq!(pool.allocate(), None);
+        assert!(!pool.contains(&test_mac("00:00:00:00:00:10")));
+        assert!(!pool.allocate_specific(test_mac("00:00:00:00:00:10")));
+
+        let mut range =
+            MacAddressRange::new(test_mac("00:00:00:00:00:10"), test_mac("00:00:00:00:00:11"));
+        range.by_ref().for_each(drop);
+        assert_eq!(MacPool::new(range).allocate(), None);
+    }
+
+    #[test]
+    fn single_address() {
+        let mac = test_mac("00:00:00:00:00:10");
+        let mut pool = MacPool::new(MacAddressRange::new(mac, mac));
+        assert_eq!(pool.allocate(), Some(mac));
+        assert_eq!(pool.allocate(), None);
+        assert!(pool.release(&mac));
+        assert_eq!(pool.allocate(), Some(mac));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn avoids_host_addresses() {
+        let host = match MacAddressIterator::new().unwrap().find(|m| !m.is_nil()) {
+            Some(host) => host,
+            None => return,
+        };
+
+        let prefix = MacPrefix::new(host, 46).unwrap();
+        let mut pool = MacPool::from_prefix(prefix);
+        pool.reserve_host_addresses().unwrap();
+
+        while let Some(mac) = pool.allocate() {
+            assert_ne!(mac, host);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut pool = MacPool::from_prefix("52:54:00:00:00:00/44".parse().unwrap());
+        pool.reserve(test_mac("52:54:00:00:00:00"));
+        pool.allocate();
+        pool.allocate();
+
+        let json = serde_json::to_string(&pool).unwrap();
+        let mut restored: MacPool = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, pool);
+        assert_eq!(restored.allocate(), pool.allocate());
+
+        let tampered = json.replace("52:54:00:00:00:03", "52:54:00:00:00:20");
+        assert!(serde_json::from_str::<MacPool>(&tampered).is_err());
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{ExpectedToken, MacAddress, MacAddressRange, MacParseError};
+use core::convert::TryFrom;
+
+/// A block of MAC addresses sharing their first `len` bits, written like an IP
+/// CIDR block, e.g. `00:11:22:00:00:00/24` for an OUI or
+/// `00:11:22:33:40:00/36` for an IEEE MA-S block.
+///
+/// The host bits of the address are always zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "alloc::borrow::Cow<'_, str>"))]
+pub struct MacPrefix {
+    network: MacAddress,
+    len: u8,
+}
+
+impl MacPrefix {
+    /// Creates the prefix of length `len` containing `addr`, clearing any host
+    /// bits of `addr`.
+    ///
+    /// Returns `None` if `len` is greater than 48.
+    pub fn new(addr: MacAddress, len: u8) -> Option<MacPrefix> {
+        if len > 48 {
+            return None;
+        }
+
+        let network = MacAddress::try_from(addr.to_u64() & mask_bits(len)).ok()?;
+        Some(MacPrefix { network, len })
+    }
+
+    /// Returns the first address of the block, with all host bits cleared.
+    pub fn network(self) -> MacAddress {
+        self.network
+    }
+
+    /// Returns the last address of the block, with all host bits set.
+    pub fn last(self) -> MacAddress {
+        self.network
+            .wrapping_add(!mask_bits(self.len) & 0xFFFF_FFFF_FFFF)
+    }
+
+    /// Returns the prefix length in bits.
+    pub fn prefix_len(self) -> u8 {
+        self.len
+    }
+
+    /// Returns the prefix length as a mask, e.g. `FF:FF:FF:00:00:00` for a
+    /// `/24`.
+    pub fn mask(self) -> MacAddress {
+        MacAddress::try_from(mask_bits(self.len)).unwrap_or_default()
+    }
+
+    /// Returns the number of addresses in the block.
+    pub fn size(self) -> u64 {
+        1 << (48 - self.len)
+    }
+
+    /// Returns `true` if `mac` is in the block.
+    pub fn contains(&self, mac: &MacAddress) -> bool {
+        mac.to_u64() & mask_bits(self.len) == self.network.to_u64()
+    }
+
+    /// Returns `true` if `other` is entirely within this block.
+    pub fn contains_prefix(&self, other: &MacPrefix) -> bool {
+        other.len >= self.len && self.contains(&other.network)
+    }
+
+    /// Returns the range of every address in the block, which can be iterated.
+    pub fn addresses(self) -> MacAddressRange {
+        MacAddressRange::new(self.network, self.last())
+    }
+
+    /// Returns the block one bit shorter that contains this one, or `None` for
+    /// a zero-length prefix.
+    pub fn supernet(self) -> Option<MacPrefix> {
+        MacPrefix::new(self.network, self.len.checked_sub(1)?)
+    }
+
+    /// Returns an iterator over the blocks of length `len` that this block
+    /// divides into.
+    ///
+    /// Returns `None` if `len` is shorter than this prefix or greater than
+    /// 48.
+    pub fn subnets(self, len: u8) -> Option<impl Iterator<Item = MacPrefix>> {
+        if len < self.len || len > 48 {
+            return None;
+        }
+
+        let count = 1u64 << (len - self.len);
+        let step = 1u64 << (48 - len);
+        let base = self.network.to_u64();
+
+        Some((0..count).map(move |i| MacPrefix {
+            network: MacAddress::try_from(base + i * step).unwrap_or_default(),
+            len,
+        }))
+    }
+}
+
+/// Returns the 48-bit mask with the top `len` bits set.
+pub(crate) fn mask_bits(len: u8) -> u64 {
+    !(0xFFFF_FFFF_FFFF >> len) & 0xFFFF_FFFF_FFFF
+}
+
+impl From<MacAddress> for MacPrefix {
+    fn from(mac: MacAddress) -> Self {
+        MacPrefix {
+            network: mac,
+            len: 48,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MacPrefix {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl core::str::FromStr for MacPrefix {
+    type Err = MacParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let slash = input.find('/').ok_or(MacParseError::InvalidLength)?;
+        let addr = input[..slash].parse()?;
+        let len = &input[slash + 1..];
+
+        if let Some((i, found)) = len.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            let offset = slash + 1 + i;
+            return Err(if found.is_ascii() {
+                MacParseError::UnexpectedChar {
+                    offset,
+                    found,
+                    expected: ExpectedToken::DecimalDigit,
+                }
+            } else {
+                MacParseError::NonAscii { offset, found }
+            });
+        }
+
+        // an empty or out of range length
+        let len = len.parse().map_err(|_| MacParseError::InvalidLength)?;
+        MacPrefix::new(addr, len).ok_or(MacParseError::InvalidLength)
+    }
+}
+
+impl TryFrom<&'_ str> for MacPrefix {
+    type Error = MacParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<alloc::borrow::Cow<'_, str>> for MacPrefix {
+    type Error = MacParseError;
+
+    fn try_from(value: alloc::borrow::Cow<'_, str>) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl core::fmt::Display for MacPrefix {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut network = [0u8; 17];
+        let mut buf = [0u8; 20];
+        buf[..17].copy_from_slice(self.network.to_str_buf(&mut network).as_bytes());
+        buf[17] = b'/';
+
+        let mut len = 18;
+        if self.len >= 10 {
+            buf[len] = b'0' + self.len / 10;
+            len += 1;
+        }
+        buf[len] = b'0' + self.len % 10;
+        len += 1;
+
+        // only ASCII was written
+        f.pad(core::str::from_utf8(&buf[..len]).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        let prefix = "00:11:22:00:00:00/24".parse::<MacPrefix>().unwrap();
+        assert_eq!(prefix.prefix_len(), 24);
+        assert_eq!(prefix.to_string(), "00:11:22:00:00:00/24");
+        assert_eq!(prefix.mask().to_string(), "FF:FF:FF:00:00:00");
+
+        let prefix = "00:11:22:33:4F:FF/36".parse::<MacPrefix>().unwrap();
+        assert_eq!(prefix.to_string(), "00:11:22:33:40:00/36");
+        assert_eq!(prefix.last().to_string(), "00:11:22:33:4F:FF");
+        assert_eq!(prefix.size(), 4096);
+
+        let prefix = "00:11:22:33:44:50/44".parse::<MacPrefix>().unwrap();
+        assert_eq!(format!("{:>22}", prefix), "  00:11:22:33:44:50/44");
+        let prefix = "00:00:00:00:00:00/0".parse::<MacPrefix>().unwrap();
+        assert_eq!(format!("{:-<21}", prefix), "00:00:00:00:00:00/0--");
+
+        assert_eq!(
+            "00:11:22:00:00:00".parse::<MacPrefix>(),
+            Err(MacParseError::InvalidLength)
+        );
+        assert_eq!(
+            "00:11:22:00:00:00/49".parse::<MacPrefix>(),
+            Err(MacParseError::InvalidLength)
+        );
+        assert_eq!(
+            "00:11:22:00:00:00/+4".parse::<MacPrefix>(),
+            Err(MacParseError::UnexpectedChar {
+                offset: 18,
+                found: '+',
+                expected: ExpectedToken::DecimalDigit
+            })
+        );
+        assert_eq!(
+            "00:11:22:00:00:00/2x".parse::<MacPrefix>(),
+            Err(MacParseError::UnexpectedChar {
+                offset: 19,
+                found: 'x',
+                expected: ExpectedToken::DecimalDigit
+            })
+        );
+        assert_eq!(
+            "00:11:22:00:00:00/".parse::<MacPrefix>(),
+            Err(MacParseError::InvalidLength)
+        );
+        assert_eq!(
+            "00:11:22:00:00:00/300".parse::<MacPrefix>(),
+            Err(MacParseError::InvalidLength)
+        );
+        assert_eq!(
+            "00:11:ZZ:00:00:00/24".parse::<MacPrefix>(),
+            Err(MacParseError::UnexpectedChar {
+                offset: 6,
+                found: 'Z',
+                expected: ExpectedToken::HexDigit
+            })
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let prefix = "00:11:22:00:00:00/24".parse::<MacPrefix>().unwrap();
+        assert!(prefix.contains(&"00:11:22:AB:CD:EF".parse().unwrap()));
+        assert!(!prefix.contains(&"00:11:23:00:00:00".parse().unwrap()));
+        assert!(prefix.contains_prefix(&"00:11:22:33:40:00/36".parse().unwrap()));
+        assert!(!prefix.contains_prefix(&"00:11:00:00:00:00/16".parse().unwrap()));
+
+        let everything = MacPrefix::new(MacAddress::BROADCAST, 0).unwrap();
+        assert_eq!(everything.prefix_len(), 0);
+        assert!(everything.contains(&MacAddress::default()));
+        assert_eq!(everything.last(), MacAddress::BROADCAST);
+
+        let exact = MacPrefix::from(MacAddress::BROADCAST);
+        assert!(exact.contains(&MacAddress::BROADCAST));
+        assert_eq!(exact.size(), 1);
+    }
+
+    #[test]
+    fn supernets_and_subnets() {
+        let prefix = "00:11:22:33:40:00/36".parse::<MacPrefix>().unwrap();
+        assert_eq!(
+            prefix.supernet().unwrap().to_string(),
+            "00:11:22:33:40:00/35"
+        );
+        assert_eq!(
+            MacPrefix::new(MacAddress::default(), 0).unwrap().supernet(),
+            None
+        );
+
+        let subnets: Vec<_> = prefix.subnets(38).unwrap().map(|p| p.to_string()).collect();
+        assert_eq!(
+            subnets,
+            [
+                "00:11:22:33:40:00/38",
+                "00:11:22:33:44:00/38",
+                "00:11:22:33:48:00/38",
+                "00:11:22:33:4C:00/38"
+            ]
+        );
+        assert!(prefix.subnets(35).is_none());
+
+        let addresses = "00:11:22:33:44:50/46"
+            .parse::<MacPrefix>()
+            .unwrap()
+            .addresses();
+        assert_eq!(addresses.len(), 4);
+        assert_eq!(addresses.end().to_string(), "00:11:22:33:44:53");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_works() {
+        use serde_test::{assert_tokens, Token};
+        let prefix: MacPrefix = "00:11:22:00:00:00/24".parse().unwrap();
+
+        assert_tokens(&prefix, &[Token::BorrowedStr("00:11:22:00:00:00/24")]);
+    }
+}
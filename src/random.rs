@@ -0,0 +1,218 @@
+use crate::siphash::SipHasher24;
+use crate::MacAddress;
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hasher};
+
+/// The SipHash key for version 1 of [`MacGenerator::generate_stable`].
+const STABLE_KEY_V1: [u8; 16] = *b"mac_address:v1\0\0";
+
+/// Generates random MAC addresses with correctly set I/G and U/L bits.
+///
+/// By default this produces locally administered unicast addresses. With a
+/// fixed OUI only the last three bytes are random and the U/L bit is left as
+/// the OUI has it, while the I/G bit always follows
+/// [`MacGenerator::multicast`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MacGenerator {
+    oui: Option<[u8; 3]>,
+    multicast: bool,
+}
+
+impl MacGenerator {
+    /// Creates a generator for locally administered unicast addresses.
+    pub fn new() -> MacGenerator {
+        MacGenerator::default()
+    }
+
+    /// Uses the given OUI for the first three bytes of every address.
+    pub fn oui(mut self, oui: [u8; 3]) -> MacGenerator {
+        self.oui = Some(oui);
+        self
+    }
+
+    /// Sets whether to generate multicast rather than unicast addresses.
+    pub fn multicast(mut self, multicast: bool) -> MacGenerator {
+        self.multicast = multicast;
+        self
+    }
+
+    /// Generates an address using randomness from the standard library's
+    /// per-process hash keys. This is fine for test fixtures and VM NICs, but
+    /// isn't suitable where the address must be unpredictable.
+    #[cfg(feature = "std")]
+    pub fn generate(&self) -> MacAddress {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u8(0);
+        let random = hasher.finish().to_le_bytes();
+
+        self.apply([
+            random[0], random[1], random[2], random[3], random[4], random[5],
+        ])
+    }
+
+    /// Generates an address using randomness from the given RNG.
+    #[cfg(feature = "rand")]
+    pub fn generate_with<R: rand::RngCore + ?Sized>(&self, rng: &mut R) -> MacAddress {
+        let mut bytes = [0u8; 6];
+        rng.fill_bytes(&mut bytes);
+
+        self.apply(bytes)
+    }
+
+    /// Deterministically derives an address from a seed, such as a machine ID,
+    /// and a name, such as an interface or guest name, in the spirit of
+    /// systemd's `MACAddressPolicy=persistent`.
+    ///
+    /// The same inputs always give the same address. The derivation is
+    /// versioned and won't change within a major release. Version 1, the
+    /// current one, takes the first six bytes (little-endian) of SipHash-2-4,
+    /// keyed with the ASCII bytes `mac_address:v1` padded with two zero bytes,
+    /// over the seed length as a little-endian `u64`, the seed and the name,
+    /// and then sets the OUI and flag bits like every other generated address.
+    pub fn generate_stable(&self, seed: &[u8], name: &str) -> MacAddress {
+        let mut hasher = SipHasher24::new(&STABLE_KEY_V1);
+        hasher.write(&(seed.len() as u64).to_le_bytes());
+        hasher.write(seed);
+        hasher.write(name.as_bytes());
+        let hash = hasher.finish().to_le_bytes();
+
+        self.apply([hash[0], hash[1], hash[2], hash[3], hash[4], hash[5]])
+    }
+
+    fn apply(&self, mut bytes: [u8; 6]) -> MacAddress {
+        match self.oui {
+            Some(oui) => bytes[..3].copy_from_slice(&oui),
+            None => bytes[0] |= 0x02,
+        }
+
+        if self.multicast {
+            bytes[0] |= 0x01;
+        } else {
+            bytes[0] &= !0x01;
+        }
+
+        MacAddress::new(bytes)
+    }
+}
+
+impl MacAddress {
+    /// Generates a random locally administered unicast address. See
+    /// [`MacGenerator`] for more control over the generated addresses.
+    #[cfg(feature = "std")]
+    pub fn random_local_unicast() -> MacAddress {
+        MacGenerator::new().generate()
+    }
+
+    /// Derives a stable locally administered unicast address from a seed and
+    /// a name. See [`MacGenerator::generate_stable`] for the derivation and
+    /// for using a fixed OUI.
+    pub fn stable_from(seed: &[u8], name: &str) -> MacAddress {
+        MacGenerator::new().generate_stable(seed, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn local_unicast() {
+        for _ in 0..64 {
+            let mac = MacAddress::random_local_unicast();
+            assert!(mac.is_local());
+            assert!(mac.is_unicast());
+        }
+
+        assert_ne!(
+            MacAddress::random_local_unicast(),
+            MacAddress::random_local_unicast()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn with_oui() {
+        let generator = MacGenerator::new().oui([0x00, 0x16, 0x3E]);
+        for _ in 0..64 {
+            let mac = generator.generate();
+            assert_eq!(mac.oui(), [0x00, 0x16, 0x3E]);
+        }
+
+        let mac = MacGenerator::new()
+            .oui([0x01, 0x00, 0x5E])
+            .multicast(false)
+            .generate();
+        assert_eq!(mac.oui(), [0x00, 0x00, 0x5E]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn multicast() {
+        let mac = MacGenerator::new().multicast(true).generate();
+        assert!(mac.is_local());
+        assert!(mac.is_multicast());
+    }
+
+    #[test]
+    fn stable() {
+        let seed = b"4a6f1c2d3e5b47a8b9c0d1e2f3a4b5c6";
+        let mac = MacAddress::stable_from(seed, "vm-101");
+        assert_eq!(mac, MacAddress::stable_from(seed, "vm-101"));
+        assert_ne!(mac, MacAddress::stable_from(seed, "vm-102"));
+        assert_ne!(mac, MacAddress::stable_from(b"other", "vm-101"));
+        assert!(mac.is_local());
+        assert!(mac.is_unicast());
+
+        // the seed length is hashed, so moving bytes between the seed and
+        // the name gives a different address
+        assert_ne!(
+            MacAddress::stable_from(b"ab", "c"),
+            MacAddress::stable_from(b"a", "bc")
+        );
+
+        let qemu = MacGenerator::new()
+            .oui([0x52, 0x54, 0x00])
+            .generate_stable(seed, "vm-101");
+        assert_eq!(qemu.oui(), [0x52, 0x54, 0x00]);
+        assert_eq!(qemu.bytes()[3..], mac.bytes()[3..]);
+    }
+
+    #[test]
+    fn stable_v1_is_fixed() {
+        assert_eq!(
+            MacAddress::stable_from(b"seed", "eth0").to_string(),
+            "E6:DB:44:AE:23:4C"
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn with_rng() {
+        struct Fixed(u8);
+
+        impl rand::RngCore for Fixed {
+            fn next_u32(&mut self) -> u32 {
+                u32::from(self.0)
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                u64::from(self.0)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                dest.iter_mut().for_each(|b| *b = self.0);
+            }
+        }
+
+        let mac = MacGenerator::new().generate_with(&mut Fixed(0xFF));
+        assert_eq!(mac.bytes(), [0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+
+        let mac = MacGenerator::new()
+            .oui([0x52, 0x54, 0x00])
+            .generate_with(&mut Fixed(0x11));
+        assert_eq!(mac.bytes(), [0x52, 0x54, 0x00, 0x11, 0x11, 0x11]);
+    }
+}
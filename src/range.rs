@@ -0,0 +1,264 @@
+use crate::MacAddress;
+use core::convert::TryFrom;
+
+/// The largest value a 48-bit MAC address can hold.
+const MAX: u64 = 0xFFFF_FFFF_FFFF;
+
+/// The error returned when converting an integer that doesn't fit in 48 bits
+/// into a [`MacAddress`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OutOfRangeError;
+
+impl core::fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("value out of range for a MAC address")
+    }
+}
+
+impl core::error::Error for OutOfRangeError {}
+
+impl MacAddress {
+    /// Returns the address as a 48-bit integer, with the first byte as the
+    /// most significant.
+    pub fn to_u64(self) -> u64 {
+        let b = self.bytes();
+        u64::from_be_bytes([0, 0, b[0], b[1], b[2], b[3], b[4], b[5]])
+    }
+
+    /// Adds `n` to the address, returning `None` on overflow past
+    /// `FF:FF:FF:FF:FF:FF`.
+    pub fn checked_add(self, n: u64) -> Option<MacAddress> {
+        self.to_u64()
+            .checked_add(n)
+            .filter(|&v| v <= MAX)
+            .map(from_u64)
+    }
+
+    /// Subtracts `n` from the address, returning `None` on underflow past
+    /// `00:00:00:00:00:00`.
+    pub fn checked_sub(self, n: u64) -> Option<MacAddress> {
+        self.to_u64().checked_sub(n).map(from_u64)
+    }
+
+    /// Adds `n` to the address, wrapping around at the 48-bit boundary.
+    pub fn wrapping_add(self, n: u64) -> MacAddress {
+        from_u64(self.to_u64().wrapping_add(n) & MAX)
+    }
+
+    /// Subtracts `n` from the address, wrapping around at the 48-bit boundary.
+    pub fn wrapping_sub(self, n: u64) -> MacAddress {
+        from_u64(self.to_u64().wrapping_sub(n) & MAX)
+    }
+
+    /// Returns the following address, or `None` for `FF:FF:FF:FF:FF:FF`.
+    pub fn next(self) -> Option<MacAddress> {
+        self.checked_add(1)
+    }
+
+    /// Returns the preceding address, or `None` for `00:00:00:00:00:00`.
+    pub fn prev(self) -> Option<MacAddress> {
+        self.checked_sub(1)
+    }
+
+    /// Returns the absolute difference between two addresses.
+    pub fn distance(self, other: MacAddress) -> u64 {
+        let (a, b) = (self.to_u64(), other.to_u64());
+        a.max(b) - a.min(b)
+    }
+}
+
+/// Converts a value already known to be at most [`MAX`].
+pub(crate) fn from_u64(v: u64) -> MacAddress {
+    let b = v.to_be_bytes();
+    MacAddress::new([b[2], b[3], b[4], b[5], b[6], b[7]])
+}
+
+impl From<MacAddress> for u64 {
+    fn from(mac: MacAddress) -> Self {
+        mac.to_u64()
+    }
+}
+
+impl TryFrom<u64> for MacAddress {
+    type Error = OutOfRangeError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        if value > MAX {
+            return Err(OutOfRangeError);
+        }
+
+        Ok(from_u64(value))
+    }
+}
+
+/// An inclusive range of MAC addresses, which iterates over every address from
+/// the start to the end.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MacAddressRange {
+    start: u64,
+    end: u64,
+    exhausted: bool,
+}
+
+impl MacAddressRange {
+    /// Creates the range of addresses from `start` to `end`, inclusive. If
+    /// `start` is greater than `end` the range is empty.
+    pub fn new(start: MacAddress, end: MacAddress) -> MacAddressRange {
+        MacAddressRange {
+            start: start.to_u64(),
+            end: end.to_u64(),
+            exhausted: start > end,
+        }
+    }
+
+    /// Creates the range of `count` consecutive addresses beginning at `base`.
+    ///
+    /// Returns `None` if `count` is zero or the range would run past
+    /// `FF:FF:FF:FF:FF:FF`.
+    pub fn from_base(base: MacAddress, count: u64) -> Option<MacAddressRange> {
+        let end = base.checked_add(count.checked_sub(1)?)?;
+        Some(MacAddressRange::new(base, end))
+    }
+
+    /// Returns the first address of the range.
+    pub fn start(&self) -> MacAddress {
+        from_u64(self.start)
+    }
+
+    /// Returns the last address of the range.
+    pub fn end(&self) -> MacAddress {
+        from_u64(self.end)
+    }
+
+    /// Returns `true` if the range has no addresses left.
+    pub fn is_empty(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Returns the number of addresses left in the range.
+    pub fn len(&self) -> u64 {
+        if self.exhausted {
+            0
+        } else {
+            self.end - self.start + 1
+        }
+    }
+
+    /// Returns `true` if `mac` is in the range.
+    pub fn contains(&self, mac: &MacAddress) -> bool {
+        let v = mac.to_u64();
+        !self.exhausted && self.start <= v && v <= self.end
+    }
+}
+
+impl Iterator for MacAddressRange {
+    type Item = MacAddress;
+
+    fn next(&mut self) -> Option<MacAddress> {
+        if self.exhausted {
+            return None;
+        }
+
+        let current = self.start;
+        if self.start == self.end {
+            self.exhausted = true;
+        } else {
+            self.start += 1;
+        }
+
+        Some(from_u64(current))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.len();
+        match usize::try_from(count) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for MacAddressRange {
+    fn next_back(&mut self) -> Option<MacAddress> {
+        if self.exhausted {
+            return None;
+        }
+
+        let current = self.end;
+        if self.start == self.end {
+            self.exhausted = true;
+        } else {
+            self.end -= 1;
+        }
+
+        Some(from_u64(current))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_conversions() {
+        let mac = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        assert_eq!(mac.to_u64(), 0x0011_2233_4455);
+        assert_eq!(u64::from(mac), 0x0011_2233_4455);
+        assert_eq!(MacAddress::try_from(0x0011_2233_4455), Ok(mac));
+        assert_eq!(
+            MacAddress::try_from(0xFFFF_FFFF_FFFF),
+            Ok(MacAddress::BROADCAST)
+        );
+        assert_eq!(
+            MacAddress::try_from(0x1_0000_0000_0000),
+            Err(OutOfRangeError)
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mac = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0xFF]);
+        assert_eq!(
+            mac.next(),
+            Some(MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x45, 0x00]))
+        );
+        assert_eq!(mac.next().unwrap().prev(), Some(mac));
+        assert_eq!(MacAddress::BROADCAST.next(), None);
+        assert_eq!(MacAddress::default().prev(), None);
+
+        assert_eq!(MacAddress::BROADCAST.checked_add(1), None);
+        assert_eq!(MacAddress::BROADCAST.wrapping_add(1), MacAddress::default());
+        assert_eq!(MacAddress::default().wrapping_sub(1), MacAddress::BROADCAST);
+        assert_eq!(mac.checked_add(0x100).unwrap().distance(mac), 0x100);
+        assert_eq!(mac.distance(mac.checked_add(0x100).unwrap()), 0x100);
+    }
+
+    #[test]
+    fn ranges() {
+        let base = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0xFE]);
+        let range = MacAddressRange::from_base(base, 4).unwrap();
+        assert_eq!(range.len(), 4);
+        assert_eq!(range.end().to_string(), "00:11:22:33:45:01");
+        assert!(range.contains(&base));
+        assert!(!range.contains(&base.prev().unwrap()));
+
+        let all: Vec<_> = range.clone().map(|m| m.to_string()).collect();
+        assert_eq!(
+            all,
+            [
+                "00:11:22:33:44:FE",
+                "00:11:22:33:44:FF",
+                "00:11:22:33:45:00",
+                "00:11:22:33:45:01"
+            ]
+        );
+        assert_eq!(range.clone().next_back(), base.checked_add(3));
+
+        assert_eq!(MacAddressRange::from_base(base, 0), None);
+        assert_eq!(MacAddressRange::from_base(MacAddress::BROADCAST, 2), None);
+
+        let top = MacAddressRange::from_base(MacAddress::BROADCAST, 1).unwrap();
+        assert_eq!(top.collect::<Vec<_>>(), [MacAddress::BROADCAST]);
+        assert!(MacAddressRange::new(MacAddress::BROADCAST, base).is_empty());
+    }
+}
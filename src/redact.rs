@@ -0,0 +1,298 @@
+use crate::range::from_u64;
+#[cfg(feature = "alloc")]
+use crate::scan::find_mac_addresses_exhaustive;
+use crate::siphash::SipHasher24;
+use crate::MacAddress;
+#[cfg(feature = "alloc")]
+use crate::MacAddressMatch;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+/// The number of Feistel rounds used by [`MacPseudonymizer`].
+const ROUNDS: u8 = 8;
+
+/// Replaces every MAC address in `text` with `replacement`.
+///
+/// Every form `FromStr` accepts is replaced, including the all-decimal and
+/// mixed-separator addresses [`find_mac_addresses`](crate::find_mac_addresses)
+/// skips, so e.g. a twelve-digit ID is redacted too, as are addresses next
+/// to punctuation or other addresses. Only addresses that are part of a
+/// longer word or of an IPv6 address, UUID or EUI-64 are left as is.
+#[cfg(feature = "alloc")]
+pub fn redact_mac_addresses<'a>(text: &'a str, replacement: &str) -> Cow<'a, str> {
+    replace_matches(text, |_, out| out.push_str(replacement))
+}
+
+/// Maps MAC addresses to stable pseudonyms using a secret key, so that logs
+/// and analytics can correlate devices without exposing their addresses.
+///
+/// The mapping is a keyed permutation of the address space, so distinct
+/// addresses always get distinct pseudonyms, and without the key a pseudonym
+/// reveals nothing about the address beyond the bits chosen to be kept. It
+/// is an 8-round Feistel network with SipHash-2-4 as the round function and
+/// won't change within a major release.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct MacPseudonymizer {
+    key: [u8; 16],
+    preserve_oui: bool,
+    preserve_flags: bool,
+}
+
+impl MacPseudonymizer {
+    /// Creates a pseudonymizer using `key`, which should be kept secret and
+    /// generated randomly.
+    pub fn new(key: [u8; 16]) -> MacPseudonymizer {
+        MacPseudonymizer {
+            key,
+            preserve_oui: false,
+            preserve_flags: false,
+        }
+    }
+
+    /// Sets whether pseudonyms keep the OUI of the address, so the vendor can
+    /// still be looked up. Only the last three bytes are then pseudonymized.
+    pub fn preserve_oui(mut self, preserve: bool) -> MacPseudonymizer {
+        self.preserve_oui = preserve;
+        self
+    }
+
+    /// Sets whether pseudonyms keep the I/G and U/L bits of the address, so
+    /// multicast and locally administered addresses can still be told apart.
+    pub fn preserve_flags(mut self, preserve: bool) -> MacPseudonymizer {
+        self.preserve_flags = preserve;
+        self
+    }
+
+    /// Returns the pseudonym for `mac`.
+    pub fn pseudonymize(&self, mac: MacAddress) -> MacAddress {
+        let value = mac.to_u64();
+
+        if self.preserve_oui {
+            let low = self.permute(value & 0xFF_FFFF, 24);
+            from_u64(value & !0xFF_FFFF | low)
+        } else if self.preserve_flags {
+            // leave out the two flag bits, the lowest bits of the first byte
+            let flags = value & 0x0300_0000_0000;
+            let rest = (value >> 42) << 40 | value & 0xFF_FFFF_FFFF;
+            let rest = self.permute(rest, 46);
+            from_u64((rest >> 40) << 42 | flags | rest & 0xFF_FFFF_FFFF)
+        } else {
+            from_u64(self.permute(value, 48))
+        }
+    }
+
+    /// Replaces every MAC address in `text` with its pseudonym, written in
+    /// the same notation and case as the original. Addresses are found as by
+    /// [`redact_mac_addresses`].
+    #[cfg(feature = "alloc")]
+    pub fn pseudonymize_text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        replace_matches(text, |m, out| {
+            write_like(m.as_str(), self.pseudonymize(m.address()), out)
+        })
+    }
+
+    /// Applies the Feistel network to the low `bits` bits of `value`, which
+    /// must be even.
+    fn permute(&self, value: u64, bits: u32) -> u64 {
+        let half = bits / 2;
+        let mask = (1 << half) - 1;
+        let (mut left, mut right) = (value >> half, value & mask);
+
+        for round in 0..ROUNDS {
+            let mut hasher = SipHasher24::new(&self.key);
+            hasher.write(&[round, bits as u8]);
+            hasher.write(&right.to_le_bytes());
+
+            let f = hasher.finish() & mask;
+            (left, right) = (right, left ^ f);
+        }
+
+        left << half | right
+    }
+}
+
+/// The key is left out so it doesn't end up in logs.
+impl core::fmt::Debug for MacPseudonymizer {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("MacPseudonymizer")
+            .field("preserve_oui", &self.preserve_oui)
+            .field("preserve_flags", &self.preserve_flags)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Replaces the MAC addresses in `text` with whatever `replace` writes for
+/// them, borrowing `text` if there are none.
+#[cfg(feature = "alloc")]
+fn replace_matches<F>(text: &str, mut replace: F) -> Cow<'_, str>
+where
+    F: FnMut(&MacAddressMatch<'_>, &mut String),
+{
+    let mut matches = find_mac_addresses_exhaustive(text).peekable();
+    if matches.peek().is_none() {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for m in matches {
+        out.push_str(&text[last..m.start()]);
+        replace(&m, &mut out);
+        last = m.end();
+    }
+    out.push_str(&text[last..]);
+
+    Cow::Owned(out)
+}
+
+/// Writes `mac` with the same separators, grouping and case as `original`.
+/// Groups of a single digit are written with as many digits as needed, as
+/// `arp` does.
+#[cfg(feature = "alloc")]
+fn write_like(original: &str, mac: MacAddress, out: &mut String) {
+    let digits = if original.bytes().any(|c| c.is_ascii_uppercase()) {
+        b"0123456789ABCDEF"
+    } else {
+        b"0123456789abcdef"
+    };
+    let hex = |n: u8| char::from(digits[usize::from(n)]);
+
+    let s = original.as_bytes();
+    let bytes = mac.bytes();
+    let mut bytes = bytes.iter().copied();
+    let mut i = 0;
+    while i < s.len() {
+        let run = s[i..].iter().take_while(|c| c.is_ascii_hexdigit()).count();
+        if run == 0 {
+            out.push(char::from(s[i]));
+            i += 1;
+            continue;
+        }
+
+        for b in bytes.by_ref().take((run / 2).max(1)) {
+            if run > 1 || b > 0x0F {
+                out.push(hex(b >> 4));
+            }
+            out.push(hex(b & 0x0F));
+        }
+        i += run;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::find_mac_addresses;
+    use crate::test_mac;
+    use std::collections::HashSet;
+
+    const KEY: [u8; 16] = *b"0123456789abcdef";
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn redact() {
+        let line = "DHCPACK to 00:1b:2c:3d:4e:5f (001B.2C3D.4E60) via eth0";
+        assert_eq!(
+            redact_mac_addresses(line, "[MAC]"),
+            "DHCPACK to [MAC] ([MAC]) via eth0"
+        );
+
+        assert_eq!(
+            redact_mac_addresses("device 001122334455 joined", "[MAC]"),
+            "device [MAC] joined"
+        );
+        assert_eq!(
+            redact_mac_addresses("id=00-1b:2c-3d:4e-5f", "[MAC]"),
+            "id=[MAC]"
+        );
+
+        for (text, expected) in &[
+            ("connected to 00:1b:2c:3d:4e:5f...", "connected to [MAC]..."),
+            ("...00:1b:2c:3d:4e:5f", "...[MAC]"),
+            (
+                "range 00:1b:2c:3d:4e:5f-00:1b:2c:3d:4e:60",
+                "range [MAC]-[MAC]",
+            ),
+            ("client 00:1b:2c:3d:4e:5f-2 joined", "client [MAC]-2 joined"),
+            ("mac 00:1b:2c:3d:4e:5f.1", "mac [MAC].1"),
+            ("key::00:1b:2c:3d:4e:5f", "key::[MAC]"),
+        ] {
+            assert_eq!(redact_mac_addresses(text, "[MAC]"), *expected);
+
+            let out = MacPseudonymizer::new(KEY).pseudonymize_text(text);
+            assert!(!out.contains("00:1b:2c:3d:4e"), "{}", out);
+        }
+
+        let clean = "no addresses at fe80::1";
+        assert!(matches!(
+            redact_mac_addresses(clean, "[MAC]"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn pseudonymize() {
+        let p = MacPseudonymizer::new(KEY);
+        let addr = test_mac("00:1B:2C:3D:4E:5F");
+
+        assert_eq!(p.pseudonymize(addr), p.pseudonymize(addr));
+        assert_ne!(p.pseudonymize(addr), addr);
+        assert_ne!(
+            MacPseudonymizer::new([0; 16]).pseudonymize(addr),
+            p.pseudonymize(addr)
+        );
+        assert_eq!(p.pseudonymize(addr), test_mac("98:C3:AF:4C:BA:8E"));
+    }
+
+    #[test]
+    fn preserves_bits() {
+        let oui = MacPseudonymizer::new(KEY).preserve_oui(true);
+        let flags = MacPseudonymizer::new(KEY).preserve_flags(true);
+
+        let mut seen = HashSet::new();
+        for i in 0..4096u32 {
+            let b = i.to_be_bytes();
+            let addr = MacAddress::new([0x03, 0x1B, 0x2C, b[1], b[2], b[3]]);
+
+            let p = oui.pseudonymize(addr);
+            assert_eq!(p.oui(), addr.oui());
+            assert!(seen.insert(p));
+
+            let p = flags.pseudonymize(addr);
+            assert!(p.is_multicast() && p.is_local());
+            let p = flags.pseudonymize(MacAddress::new([0xFC, b[1], b[2], b[3], 0, 0]));
+            assert!(p.is_unicast() && p.is_universal());
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn pseudonymize_text() {
+        let p = MacPseudonymizer::new(KEY);
+        let line = "arp 0:1b:2c:3:4:5 cisco 001B.2C3D.4E5F bare 001b2c3d4e5f";
+        let out = p.pseudonymize_text(line);
+
+        let original: Vec<_> = find_mac_addresses(line).collect();
+        let replaced: Vec<_> = find_mac_addresses(&out).collect();
+        assert_eq!(original.len(), replaced.len());
+
+        for (o, r) in original.iter().zip(replaced.iter()) {
+            assert_eq!(r.address(), p.pseudonymize(o.address()));
+        }
+
+        assert_eq!(replaced[1].as_str().len(), 14);
+        assert_eq!(&replaced[1].as_str()[4..5], ".");
+        assert!(!replaced[1]
+            .as_str()
+            .contains(|c: char| c.is_ascii_lowercase()));
+        assert!(!replaced[2]
+            .as_str()
+            .contains(|c: char| c.is_ascii_uppercase()));
+        assert!(out.starts_with("arp ") && out.contains(" cisco ") && out.contains(" bare "));
+
+        let out = p.pseudonymize_text("device 001122334455 id=00-1b:2c-3d:4e-5f");
+        let decimal = p.pseudonymize(test_mac("001122334455"));
+        assert_eq!(out, format!("device {:#x} id=98-c3:af-4c:ba-8e", decimal));
+    }
+}
@@ -0,0 +1,262 @@
+use crate::parse::{parse_bytes, parse_leading};
+use crate::{MacAddress, ParseOptions};
+use core::net::Ipv6Addr;
+
+/// The furthest a run of hex digits and separators is followed on either side
+/// of a match, which is more than the length of any identifier it could be
+/// part of.
+const MAX_RUN: usize = 64;
+
+/// Returns an iterator over the MAC addresses in `text`, such as a log line or
+/// the output of a `show` command.
+///
+/// Every form [`ParseOptions::lenient`] accepts is recognized, as long as
+/// the address isn't part of a longer word, such as a hash, or of an IPv6
+/// address, UUID or EUI-64, so these don't give false positives. An
+/// address must use the same separator throughout, and one without separators
+/// or in Cisco's dotted form must contain a hex letter, since twelve decimal
+/// digits are far more likely to be a timestamp, version or ID.
+pub fn find_mac_addresses(text: &str) -> MacAddressMatches<'_> {
+    MacAddressMatches {
+        text,
+        pos: 0,
+        exhaustive: false,
+    }
+}
+
+/// Like [`find_mac_addresses`], but also finds all-decimal addresses and ones
+/// with mixed separators, so that every form `FromStr` accepts is found
+/// unless it's part of a longer word or an IPv6 address, UUID or EUI-64.
+#[cfg(feature = "alloc")]
+pub(crate) fn find_mac_addresses_exhaustive(text: &str) -> MacAddressMatches<'_> {
+    MacAddressMatches {
+        text,
+        pos: 0,
+        exhaustive: true,
+    }
+}
+
+/// A MAC address found in text by [`find_mac_addresses`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MacAddressMatch<'a> {
+    address: MacAddress,
+    text: &'a str,
+    start: usize,
+}
+
+impl<'a> MacAddressMatch<'a> {
+    /// Returns the address that was found.
+    pub fn address(&self) -> MacAddress {
+        self.address
+    }
+
+    /// Returns the address as written in the text.
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the byte offset in the text where the address starts.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the byte offset in the text just past the end of the address.
+    pub fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+
+    /// Returns the byte span of the address in the text.
+    pub fn range(&self) -> core::ops::Range<usize> {
+        self.start()..self.end()
+    }
+}
+
+/// An iterator over the MAC addresses in some text, returned by
+/// [`find_mac_addresses`].
+#[derive(Debug, Clone)]
+pub struct MacAddressMatches<'a> {
+    text: &'a str,
+    pos: usize,
+    exhaustive: bool,
+}
+
+impl<'a> Iterator for MacAddressMatches<'a> {
+    type Item = MacAddressMatch<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.text.as_bytes();
+        let options = ParseOptions::lenient().strict(!self.exhaustive);
+
+        while self.pos < s.len() {
+            let start = self.pos;
+            self.pos += 1;
+
+            if !s[start].is_ascii_hexdigit() || (start > 0 && is_word(s[start - 1])) {
+                continue;
+            }
+
+            let mut bytes = [0u8; 6];
+            let end = match parse_leading(&s[start..], &mut bytes, options) {
+                Ok(len) => start + len,
+                Err(_) => continue,
+            };
+
+            if (end < s.len() && is_word(s[end]))
+                || (!self.exhaustive && is_decimal(&s[start..end]))
+                || within_identifier(s, start, end)
+            {
+                continue;
+            }
+
+            self.pos = end;
+            return Some(MacAddressMatch {
+                address: MacAddress::new(bytes),
+                // the address is ASCII, so these are character boundaries
+                text: &self.text[start..end],
+                start,
+            });
+        }
+
+        None
+    }
+}
+
+fn is_separator(c: u8) -> bool {
+    c == b':' || c == b'-' || c == b'.'
+}
+
+fn is_word(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+/// Returns `true` if `s` is made of decimal digits and any dots, such as
+/// `202401151234` or `2024.0115.1234`.
+fn is_decimal(s: &[u8]) -> bool {
+    s.iter().all(|c| c.is_ascii_digit() || *c == b'.')
+}
+
+/// Returns `true` if the run of hex digits and separators around
+/// `s[start..end]` is a longer identifier the match is only part of: an IPv6
+/// address, EUI-64 or UUID.
+fn within_identifier(s: &[u8], start: usize, end: usize) -> bool {
+    let in_run = |c: &&u8| c.is_ascii_hexdigit() || is_separator(**c);
+    let before = s[..start].iter().rev().take_while(in_run).take(MAX_RUN + 1);
+    let after = s[end..].iter().take_while(in_run).take(MAX_RUN + 1);
+    let (before, after) = (before.count(), after.count());
+
+    // longer than any identifier
+    if before > MAX_RUN || after > MAX_RUN {
+        return false;
+    }
+
+    let (mut lo, mut hi) = (start - before, end + after);
+
+    // a group joined to a word, as in `key::` or `uuid:`, isn't part of it
+    if lo > 0 && is_word(s[lo - 1]) {
+        if let Some(i) = s[lo..start].iter().position(|&c| is_separator(c)) {
+            lo += i + 1;
+        }
+    }
+    if hi < s.len() && is_word(s[hi]) {
+        if let Some(i) = s[end..hi].iter().rposition(|&c| is_separator(c)) {
+            hi = end + i;
+        }
+    }
+
+    // punctuation, such as the end of a sentence or an ellipsis
+    while lo < start && s[lo] == b'.' {
+        lo += 1;
+    }
+    while hi > end && s[hi - 1] == b'.' {
+        hi -= 1;
+    }
+
+    (lo, hi) != (start, end) && is_identifier(&s[lo..hi])
+}
+
+/// Returns `true` if `s` is an IPv6 address, EUI-64 or UUID.
+fn is_identifier(s: &[u8]) -> bool {
+    let is_uuid = s.len() == 36
+        && s.iter().enumerate().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => *c == b'-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    let is_eui64 = parse_bytes(s, &mut [0u8; 8], ParseOptions::lenient().strict(true)).is_ok();
+    let is_ipv6 = core::str::from_utf8(s).is_ok_and(|s| s.parse::<Ipv6Addr>().is_ok());
+
+    is_uuid || is_eui64 || is_ipv6
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(text: &str) -> Vec<&str> {
+        find_mac_addresses(text).map(|m| m.as_str()).collect()
+    }
+
+    #[test]
+    fn finds_all_forms() {
+        let line = "DHCPACK on 10.0.0.5 to 00:1b:2c:3d:4e:5f (host) via eth0";
+        let m = find_mac_addresses(line).next().unwrap();
+        assert_eq!(m.address(), "00:1B:2C:3D:4E:5F".parse().unwrap());
+        assert_eq!(m.range(), 23..40);
+        assert_eq!(&line[m.range()], "00:1b:2c:3d:4e:5f");
+
+        assert_eq!(
+            found("cisco 001b.2c3d.4e5f, arp 0:1b:2c:3:4:5, bare 001B2C3D4E5F."),
+            ["001b.2c3d.4e5f", "0:1b:2c:3:4:5", "001B2C3D4E5F"]
+        );
+        assert_eq!(
+            found("mac=00-1B-2C-3D-4E-5F;peer:00:1b:2c:3d:4e:60"),
+            ["00-1B-2C-3D-4E-5F", "00:1b:2c:3d:4e:60"]
+        );
+    }
+
+    #[test]
+    fn ignores_longer_hex() {
+        for text in &[
+            "fe80::21b:2cff:fe3d:4e5f",
+            "fe80::1:2:3:4:5:6",
+            "2001:db8:0:1:2:3:4:5",
+            "123e4567-e89b-12d3-a456-426614174000",
+            "commit 0123456789abcdef0123456789abcdef01234567",
+            "eui64 00:1b:2c:3d:4e:5f:60:71",
+            "2024-01-15T12:34:56.789Z took 12:34:56:78:90:12ms",
+            "request id 202401151234",
+            "build 2024.0115.1234 done",
+            "00:1b:2c:3d:4e:5f6",
+            "00:1b-2c:3d:4e:5f",
+            "urn:uuid:123e4567-e89b-12d3-a456-42661417400a",
+            "[fe80::1:2:3:4:5:6]:546 and fe80::a:b:c:d:e:f.",
+            "eui 001b.2c3d.4e5f.6071",
+        ] {
+            assert_eq!(found(text), Vec::<&str>::new(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn finds_next_to_punctuation() {
+        for (text, expected) in &[
+            (
+                "connected to 00:1b:2c:3d:4e:5f...",
+                &["00:1b:2c:3d:4e:5f"][..],
+            ),
+            ("...00:1b:2c:3d:4e:5f", &["00:1b:2c:3d:4e:5f"]),
+            (
+                "range 00:1b:2c:3d:4e:5f-00:1b:2c:3d:4e:60",
+                &["00:1b:2c:3d:4e:5f", "00:1b:2c:3d:4e:60"],
+            ),
+            (
+                "range 00-1b-2c-3d-4e-5f-00-1b-2c-3d-4e-60",
+                &["00-1b-2c-3d-4e-5f", "00-1b-2c-3d-4e-60"],
+            ),
+            ("client 00:1b:2c:3d:4e:5f-2 joined", &["00:1b:2c:3d:4e:5f"]),
+            ("mac 00:1b:2c:3d:4e:5f.1", &["00:1b:2c:3d:4e:5f"]),
+            ("key::00:1b:2c:3d:4e:5f", &["00:1b:2c:3d:4e:5f"]),
+            ("at 001b.2c3d.4e5f.", &["001b.2c3d.4e5f"]),
+        ] {
+            assert_eq!(found(text), *expected, "{}", text);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+/// An incremental SipHash-2-4 hasher.
+pub(crate) struct SipHasher24 {
+    v: [u64; 4],
+    tail: [u8; 8],
+    ntail: usize,
+    length: usize,
+}
+
+impl SipHasher24 {
+    pub(crate) fn new(key: &[u8; 16]) -> SipHasher24 {
+        let mut k0 = [0u8; 8];
+        let mut k1 = [0u8; 8];
+        k0.copy_from_slice(&key[..8]);
+        k1.copy_from_slice(&key[8..]);
+        let k0 = u64::from_le_bytes(k0);
+        let k1 = u64::from_le_bytes(k1);
+
+        SipHasher24 {
+            v: [
+                k0 ^ 0x736f_6d65_7073_6575,
+                k1 ^ 0x646f_7261_6e64_6f6d,
+                k0 ^ 0x6c79_6765_6e65_7261,
+                k1 ^ 0x7465_6462_7974_6573,
+            ],
+            tail: [0; 8],
+            ntail: 0,
+            length: 0,
+        }
+    }
+
+    pub(crate) fn write(&mut self, mut data: &[u8]) {
+        self.length += data.len();
+
+        if self.ntail != 0 {
+            let fill = (8 - self.ntail).min(data.len());
+            self.tail[self.ntail..self.ntail + fill].copy_from_slice(&data[..fill]);
+            self.ntail += fill;
+            data = &data[fill..];
+
+            if self.ntail < 8 {
+                return;
+            }
+
+            self.compress(u64::from_le_bytes(self.tail));
+            self.ntail = 0;
+        }
+
+        let mut chunks = data.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut m = [0u8; 8];
+            m.copy_from_slice(chunk);
+            self.compress(u64::from_le_bytes(m));
+        }
+
+        let rest = chunks.remainder();
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.ntail = rest.len();
+    }
+
+    pub(crate) fn finish(mut self) -> u64 {
+        let mut last = [0u8; 8];
+        last[..self.ntail].copy_from_slice(&self.tail[..self.ntail]);
+        last[7] = self.length as u8;
+        self.compress(u64::from_le_bytes(last));
+
+        self.v[2] ^= 0xFF;
+        for _ in 0..4 {
+            self.round();
+        }
+
+        self.v[0] ^ self.v[1] ^ self.v[2] ^ self.v[3]
+    }
+
+    fn compress(&mut self, m: u64) {
+        self.v[3] ^= m;
+        self.round();
+        self.round();
+        self.v[0] ^= m;
+    }
+
+    fn round(&mut self) {
+        let [mut v0, mut v1, mut v2, mut v3] = self.v;
+
+        v0 = v0.wrapping_add(v1);
+        v1 = v1.rotate_left(13);
+        v1 ^= v0;
+        v0 = v0.rotate_left(32);
+        v2 = v2.wrapping_add(v3);
+        v3 = v3.rotate_left(16);
+        v3 ^= v2;
+        v0 = v0.wrapping_add(v3);
+        v3 = v3.rotate_left(21);
+        v3 ^= v0;
+        v2 = v2.wrapping_add(v1);
+        v1 = v1.rotate_left(17);
+        v1 ^= v2;
+        v2 = v2.rotate_left(32);
+
+        self.v = [v0, v1, v2, v3];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_vectors() {
+        let mut key = [0u8; 16];
+        key.iter_mut().enumerate().for_each(|(i, k)| *k = i as u8);
+        let input: Vec<u8> = (0..15).collect();
+
+        assert_eq!(SipHasher24::new(&key).finish(), 0x726f_db47_dd0e_0e31);
+
+        let mut hasher = SipHasher24::new(&key);
+        hasher.write(&input);
+        assert_eq!(hasher.finish(), 0xa129_ca61_49be_45e5);
+
+        // split writes must match a single write
+        let mut hasher = SipHasher24::new(&key);
+        hasher.write(&input[..3]);
+        hasher.write(&input[3..12]);
+        hasher.write(&input[12..]);
+        assert_eq!(hasher.finish(), 0xa129_ca61_49be_45e5);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::prefix::mask_bits;
+use crate::{MacAddress, MacPrefix};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::iter::FromIterator;
+
+/// A map from MAC prefixes of any length to values, looked up by the longest
+/// prefix containing an address.
+///
+/// Entries are grouped by prefix length, so a lookup costs one hash probe per
+/// distinct length in the table (e.g. three for a table of IEEE MA-L, MA-M and
+/// MA-S blocks) regardless of how many entries it holds.
+#[derive(Debug, Clone)]
+pub struct MacTable<V> {
+    // sorted by prefix length, longest first
+    levels: Vec<Level<V>>,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Level<V> {
+    prefix_len: u8,
+    mask: u64,
+    entries: HashMap<u64, V, BuildHasherDefault<KeyHasher>>,
+}
+
+impl<V> MacTable<V> {
+    /// Creates an empty table.
+    pub fn new() -> MacTable<V> {
+        MacTable {
+            levels: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of prefixes in the table.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the table has no prefixes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a value for `prefix`, returning the previous value for exactly
+    /// that prefix.
+    pub fn insert(&mut self, prefix: MacPrefix, value: V) -> Option<V> {
+        let prefix_len = prefix.prefix_len();
+        let i = match self
+            .levels
+            .binary_search_by(|level| prefix_len.cmp(&level.prefix_len))
+        {
+            Ok(i) => i,
+            Err(i) => {
+                self.levels.insert(
+                    i,
+                    Level {
+                        prefix_len,
+                        mask: mask_bits(prefix_len),
+                        entries: HashMap::default(),
+                    },
+                );
+                i
+            }
+        };
+
+        let old = self.levels[i]
+            .entries
+            .insert(prefix.network().to_u64(), value);
+        if old.is_none() {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    /// Removes and returns the value for exactly `prefix`.
+    pub fn remove(&mut self, prefix: &MacPrefix) -> Option<V> {
+        let i = self.level(prefix.prefix_len())?;
+        let old = self.levels[i].entries.remove(&prefix.network().to_u64())?;
+        self.len -= 1;
+
+        if self.levels[i].entries.is_empty() {
+            self.levels.remove(i);
+        }
+
+        Some(old)
+    }
+
+    /// Returns the value for exactly `prefix`.
+    pub fn get(&self, prefix: &MacPrefix) -> Option<&V> {
+        let i = self.level(prefix.prefix_len())?;
+        self.levels[i].entries.get(&prefix.network().to_u64())
+    }
+
+    /// Returns the value of the longest prefix containing `mac`.
+    pub fn lookup(&self, mac: &MacAddress) -> Option<&V> {
+        let mac = mac.to_u64();
+
+        self.levels
+            .iter()
+            .find_map(|level| level.entries.get(&(mac & level.mask)))
+    }
+
+    /// Returns the longest prefix containing `mac` along with its value.
+    pub fn lookup_prefix(&self, mac: &MacAddress) -> Option<(MacPrefix, &V)> {
+        let bits = mac.to_u64();
+
+        self.levels.iter().find_map(|level| {
+            let value = level.entries.get(&(bits & level.mask))?;
+            Some((MacPrefix::new(*mac, level.prefix_len)?, value))
+        })
+    }
+
+    /// Returns an iterator over every prefix and its value, longest prefixes
+    /// first and otherwise in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (MacPrefix, &V)> + '_ {
+        self.levels.iter().flat_map(|level| {
+            level.entries.iter().filter_map(move |(&network, value)| {
+                let network = MacAddress::try_from(network).ok()?;
+                Some((MacPrefix::new(network, level.prefix_len)?, value))
+            })
+        })
+    }
+
+    fn level(&self, prefix_len: u8) -> Option<usize> {
+        self.levels
+            .binary_search_by(|level| prefix_len.cmp(&level.prefix_len))
+            .ok()
+    }
+}
+
+impl<V> Default for MacTable<V> {
+    fn default() -> Self {
+        MacTable::new()
+    }
+}
+
+impl<V> Extend<(MacPrefix, V)> for MacTable<V> {
+    fn extend<I: IntoIterator<Item = (MacPrefix, V)>>(&mut self, iter: I) {
+        for (prefix, value) in iter {
+            self.insert(prefix, value);
+        }
+    }
+}
+
+impl<V> FromIterator<(MacPrefix, V)> for MacTable<V> {
+    fn from_iter<I: IntoIterator<Item = (MacPrefix, V)>>(iter: I) -> Self {
+        let mut table = MacTable::new();
+        table.extend(iter);
+        table
+    }
+}
+
+/// A cheap hasher for the 48-bit keys of a [`MacTable`], using the MurmurHash3
+/// finalizer so that the zeroed host bits of short prefixes are still spread
+/// across buckets. Keys come from the table's own configuration, so there's no
+/// need for a collision-resistant hash.
+#[derive(Default)]
+struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.write_u64(u64::from(b));
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        let mut x = self.0 ^ n;
+        x ^= x >> 33;
+        x = x.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+        x ^= x >> 33;
+        x = x.wrapping_mul(0xC4CE_B9FE_1A85_EC53);
+        x ^= x >> 33;
+        self.0 = x;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_mac;
+
+    fn prefix(s: &str) -> MacPrefix {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn longest_prefix_wins() {
+        let mut table: MacTable<&str> = vec![
+            (prefix("00:00:00:00:00:00/0"), "default"),
+            (prefix("00:11:22:00:00:00/24"), "vendor"),
+            (prefix("00:11:22:30:00:00/28"), "ma-m"),
+            (prefix("00:11:22:33:40:00/36"), "ma-s"),
+            (MacPrefix::from(test_mac("00:11:22:33:44:55")), "device"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(table.len(), 5);
+        assert_eq!(
+            table.lookup(&test_mac("00:11:22:33:44:55")),
+            Some(&"device")
+        );
+        assert_eq!(table.lookup(&test_mac("00:11:22:33:44:56")), Some(&"ma-s"));
+        assert_eq!(table.lookup(&test_mac("00:11:22:34:00:00")), Some(&"ma-m"));
+        assert_eq!(
+            table.lookup(&test_mac("00:11:22:44:00:00")),
+            Some(&"vendor")
+        );
+        assert_eq!(
+            table.lookup(&test_mac("48:27:E2:44:25:D8")),
+            Some(&"default")
+        );
+        assert_eq!(
+            table.lookup_prefix(&test_mac("00:11:22:33:4A:BC")),
+            Some((prefix("00:11:22:33:40:00/36"), &"ma-s"))
+        );
+
+        assert_eq!(
+            table.remove(&prefix("00:00:00:00:00:00/0")),
+            Some("default")
+        );
+        assert_eq!(table.lookup(&test_mac("48:27:E2:44:25:D8")), None);
+        assert_eq!(table.len(), 4);
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let mut table = MacTable::new();
+        assert!(table.is_empty());
+        assert_eq!(table.insert(prefix("00:11:22:00:00:00/24"), 1), None);
+        assert_eq!(table.insert(prefix("00:11:22:FF:FF:FF/24"), 2), Some(1));
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.get(&prefix("00:11:22:00:00:00/24")), Some(&2));
+        assert_eq!(table.get(&prefix("00:11:22:00:00:00/28")), None);
+        assert_eq!(table.remove(&prefix("00:11:22:00:00:00/28")), None);
+        assert_eq!(table.remove(&prefix("00:11:22:00:00:00/24")), Some(2));
+        assert!(table.is_empty());
+        assert!(table.levels.is_empty());
+    }
+
+    #[test]
+    fn iter() {
+        let table: MacTable<u32> = vec![
+            (prefix("00:11:22:00:00:00/24"), 24),
+            (prefix("00:11:22:33:40:00/36"), 36),
+        ]
+        .into_iter()
+        .collect();
+
+        let entries: Vec<_> = table.iter().map(|(p, v)| (p.to_string(), *v)).collect();
+        assert_eq!(
+            entries,
+            [
+                ("00:11:22:33:40:00/36".to_string(), 36),
+                ("00:11:22:00:00:00/24".to_string(), 24)
+            ]
+        );
+    }
+}
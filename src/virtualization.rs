@@ -0,0 +1,71 @@
+use crate::MacAddress;
+
+/// A hypervisor or container runtime that a MAC address prefix is known to be
+/// assigned by, as returned by [`MacAddress::virtualization_hint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Virtualization {
+    /// QEMU/KVM, `52:54:00`.
+    Qemu,
+    /// VMware, `00:50:56`, `00:0C:29`, `00:05:69` and `00:1C:14`.
+    VMware,
+    /// Oracle VirtualBox, `08:00:27` and `0A:00:27` for host-only adapters.
+    VirtualBox,
+    /// Microsoft Hyper-V, `00:15:5D`.
+    HyperV,
+    /// Microsoft Virtual PC, `00:03:FF`.
+    VirtualPc,
+    /// Xen, `00:16:3E`. LXC and LXD also default to this prefix.
+    Xen,
+    /// Parallels, `00:1C:42`.
+    Parallels,
+    /// bhyve, `58:9C:FC`.
+    Bhyve,
+    /// Docker bridge networks, `02:42`.
+    Docker,
+}
+
+impl MacAddress {
+    /// Returns the hypervisor or container runtime whose well-known prefix
+    /// this address starts with.
+    ///
+    /// This is only a hint: these prefixes can be configured on physical
+    /// hardware, and virtual NICs can be given arbitrary addresses.
+    pub fn virtualization_hint(self) -> Option<Virtualization> {
+        let hint = match self.oui() {
+            [0x52, 0x54, 0x00] => Virtualization::Qemu,
+            [0x00, 0x50, 0x56] | [0x00, 0x0C, 0x29] | [0x00, 0x05, 0x69] | [0x00, 0x1C, 0x14] => {
+                Virtualization::VMware
+            }
+            [0x08, 0x00, 0x27] | [0x0A, 0x00, 0x27] => Virtualization::VirtualBox,
+            [0x00, 0x15, 0x5D] => Virtualization::HyperV,
+            [0x00, 0x03, 0xFF] => Virtualization::VirtualPc,
+            [0x00, 0x16, 0x3E] => Virtualization::Xen,
+            [0x00, 0x1C, 0x42] => Virtualization::Parallels,
+            [0x58, 0x9C, 0xFC] => Virtualization::Bhyve,
+            [0x02, 0x42, _] => Virtualization::Docker,
+            _ => return None,
+        };
+
+        Some(hint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints() {
+        let hint = |s: &str| s.parse::<MacAddress>().unwrap().virtualization_hint();
+
+        assert_eq!(hint("52:54:00:12:34:56"), Some(Virtualization::Qemu));
+        assert_eq!(hint("00:0c:29:ab:cd:ef"), Some(Virtualization::VMware));
+        assert_eq!(hint("08:00:27:00:00:01"), Some(Virtualization::VirtualBox));
+        assert_eq!(hint("00:15:5D:01:02:03"), Some(Virtualization::HyperV));
+        assert_eq!(hint("00:16:3E:01:02:03"), Some(Virtualization::Xen));
+        assert_eq!(hint("00:1C:42:01:02:03"), Some(Virtualization::Parallels));
+        assert_eq!(hint("02:42:AC:11:00:02"), Some(Virtualization::Docker));
+        assert_eq!(hint("48:27:E2:44:25:D8"), None);
+    }
+}
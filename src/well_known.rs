@@ -0,0 +1,190 @@
+use crate::MacAddress;
+
+/// A reserved or well-known MAC address, or range of addresses, as returned by
+/// [`MacAddress::well_known`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WellKnownAddress {
+    /// The broadcast address, `FF:FF:FF:FF:FF:FF`.
+    Broadcast,
+    /// `01:80:C2:00:00:00`, the Spanning Tree Protocol bridge group address.
+    Stp,
+    /// `01:80:C2:00:00:01`, IEEE 802.3 MAC control frames such as pause
+    /// frames.
+    MacControl,
+    /// `01:80:C2:00:00:02`, IEEE 802.3 slow protocols such as LACP and OAM.
+    SlowProtocols,
+    /// `01:80:C2:00:00:03`, the IEEE 802.1X port access entity group address.
+    Pae,
+    /// `01:80:C2:00:00:08`, the provider bridge group address used by IEEE
+    /// 802.1ad.
+    ProviderBridge,
+    /// `01:80:C2:00:00:0E`, the nearest bridge group address used by LLDP and
+    /// PTP peer delay messages.
+    Lldp,
+    /// Any other address in the IEEE 802.1 reserved range
+    /// `01:80:C2:00:00:00` to `01:80:C2:00:00:0F`, with its last byte.
+    Reserved8021(u8),
+    /// `01:80:C2:00:00:20`, the GARP/MRP multicast registration address.
+    Mmrp,
+    /// `01:80:C2:00:00:21`, the GARP/MRP VLAN registration address.
+    Mvrp,
+    /// `01:00:0C:CC:CC:CC`, used by CDP, VTP, DTP, PAgP and UDLD.
+    CiscoDiscovery,
+    /// `01:00:0C:CC:CC:CD`, Cisco's per-VLAN spanning tree (PVST+).
+    CiscoPvst,
+    /// `01:1B:19:00:00:00`, the Precision Time Protocol (IEEE 1588) group
+    /// address.
+    Ptp,
+    /// The IANA unicast block, `00:00:5E:00:00:00` to `00:00:5E:FF:FF:FF`.
+    IanaUnicast,
+    /// The IPv4 multicast block, `01:00:5E:00:00:00` to `01:00:5E:7F:FF:FF`.
+    Ipv4Multicast,
+    /// The rest of the IANA multicast block, `01:00:5E:80:00:00` to
+    /// `01:00:5E:FF:FF:FF`.
+    IanaMulticast,
+    /// The IPv6 multicast block, `33:33:00:00:00:00` to `33:33:FF:FF:FF:FF`.
+    Ipv6Multicast,
+}
+
+impl WellKnownAddress {
+    /// Returns `true` for the IEEE 802.1 reserved group addresses
+    /// (`01:80:C2:00:00:00` to `01:80:C2:00:00:0F`), which a MAC bridge must
+    /// never forward.
+    pub fn is_link_local(self) -> bool {
+        matches!(
+            self,
+            WellKnownAddress::Stp
+                | WellKnownAddress::MacControl
+                | WellKnownAddress::SlowProtocols
+                | WellKnownAddress::Pae
+                | WellKnownAddress::ProviderBridge
+                | WellKnownAddress::Lldp
+                | WellKnownAddress::Reserved8021(_)
+        )
+    }
+}
+
+impl MacAddress {
+    /// The broadcast address, `FF:FF:FF:FF:FF:FF`.
+    pub const BROADCAST: MacAddress = MacAddress { bytes: [0xFF; 6] };
+    /// The Spanning Tree Protocol bridge group address, `01:80:C2:00:00:00`.
+    pub const STP: MacAddress = ieee8021(0x00);
+    /// The IEEE 802.3 MAC control (pause frame) address, `01:80:C2:00:00:01`.
+    pub const MAC_CONTROL: MacAddress = ieee8021(0x01);
+    /// The IEEE 802.3 slow protocols (LACP, OAM) address, `01:80:C2:00:00:02`.
+    pub const SLOW_PROTOCOLS: MacAddress = ieee8021(0x02);
+    /// The IEEE 802.1X port access entity address, `01:80:C2:00:00:03`.
+    pub const PAE: MacAddress = ieee8021(0x03);
+    /// The IEEE 802.1ad provider bridge group address, `01:80:C2:00:00:08`.
+    pub const PROVIDER_BRIDGE: MacAddress = ieee8021(0x08);
+    /// The LLDP nearest bridge address, `01:80:C2:00:00:0E`.
+    pub const LLDP: MacAddress = ieee8021(0x0E);
+    /// The MMRP/GMRP address, `01:80:C2:00:00:20`.
+    pub const MMRP: MacAddress = ieee8021(0x20);
+    /// The MVRP/GVRP address, `01:80:C2:00:00:21`.
+    pub const MVRP: MacAddress = ieee8021(0x21);
+    /// The Cisco CDP/VTP address, `01:00:0C:CC:CC:CC`.
+    pub const CISCO_DISCOVERY: MacAddress = MacAddress {
+        bytes: [0x01, 0x00, 0x0C, 0xCC, 0xCC, 0xCC],
+    };
+    /// The Cisco PVST+ address, `01:00:0C:CC:CC:CD`.
+    pub const CISCO_PVST: MacAddress = MacAddress {
+        bytes: [0x01, 0x00, 0x0C, 0xCC, 0xCC, 0xCD],
+    };
+    /// The Precision Time Protocol address, `01:1B:19:00:00:00`.
+    pub const PTP: MacAddress = MacAddress {
+        bytes: [0x01, 0x1B, 0x19, 0x00, 0x00, 0x00],
+    };
+
+    /// Classifies this address if it's a reserved or well-known address.
+    pub fn well_known(self) -> Option<WellKnownAddress> {
+        let b = self.bytes;
+
+        let known = match b {
+            [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF] => WellKnownAddress::Broadcast,
+            [0x01, 0x80, 0xC2, 0x00, 0x00, last] => match last {
+                0x00 => WellKnownAddress::Stp,
+                0x01 => WellKnownAddress::MacControl,
+                0x02 => WellKnownAddress::SlowProtocols,
+                0x03 => WellKnownAddress::Pae,
+                0x08 => WellKnownAddress::ProviderBridge,
+                0x0E => WellKnownAddress::Lldp,
+                0x04..=0x0F => WellKnownAddress::Reserved8021(last),
+                0x20 => WellKnownAddress::Mmrp,
+                0x21 => WellKnownAddress::Mvrp,
+                _ => return None,
+            },
+            [0x01, 0x00, 0x0C, 0xCC, 0xCC, 0xCC] => WellKnownAddress::CiscoDiscovery,
+            [0x01, 0x00, 0x0C, 0xCC, 0xCC, 0xCD] => WellKnownAddress::CiscoPvst,
+            [0x01, 0x1B, 0x19, 0x00, 0x00, 0x00] => WellKnownAddress::Ptp,
+            [0x00, 0x00, 0x5E, ..] => WellKnownAddress::IanaUnicast,
+            [0x01, 0x00, 0x5E, b3, ..] if b3 & 0x80 == 0 => WellKnownAddress::Ipv4Multicast,
+            [0x01, 0x00, 0x5E, ..] => WellKnownAddress::IanaMulticast,
+            [0x33, 0x33, ..] => WellKnownAddress::Ipv6Multicast,
+            _ => return None,
+        };
+
+        Some(known)
+    }
+}
+
+const fn ieee8021(last: u8) -> MacAddress {
+    MacAddress {
+        bytes: [0x01, 0x80, 0xC2, 0x00, 0x00, last],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify() {
+        assert_eq!(
+            MacAddress::BROADCAST.well_known(),
+            Some(WellKnownAddress::Broadcast)
+        );
+        assert_eq!(MacAddress::STP.well_known(), Some(WellKnownAddress::Stp));
+        assert_eq!(MacAddress::LLDP.well_known(), Some(WellKnownAddress::Lldp));
+        assert_eq!(
+            MacAddress::new([0x01, 0x80, 0xC2, 0x00, 0x00, 0x0D]).well_known(),
+            Some(WellKnownAddress::Reserved8021(0x0D))
+        );
+        assert_eq!(
+            MacAddress::CISCO_DISCOVERY.well_known(),
+            Some(WellKnownAddress::CiscoDiscovery)
+        );
+        assert_eq!(
+            MacAddress::new([0x01, 0x00, 0x5E, 0x7F, 0x00, 0x01]).well_known(),
+            Some(WellKnownAddress::Ipv4Multicast)
+        );
+        assert_eq!(
+            MacAddress::new([0x01, 0x00, 0x5E, 0x80, 0x00, 0x01]).well_known(),
+            Some(WellKnownAddress::IanaMulticast)
+        );
+        assert_eq!(
+            MacAddress::new([0x00, 0x00, 0x5E, 0x00, 0x01, 0x01]).well_known(),
+            Some(WellKnownAddress::IanaUnicast)
+        );
+        assert_eq!(
+            MacAddress::new([0x01, 0x80, 0xC2, 0x00, 0x00, 0x10]).well_known(),
+            None
+        );
+        assert_eq!(
+            MacAddress::new([0x48, 0x27, 0xE2, 0x44, 0x25, 0xD8]).well_known(),
+            None
+        );
+    }
+
+    #[test]
+    fn link_local() {
+        for last in 0x00..=0x0F {
+            let mac = MacAddress::new([0x01, 0x80, 0xC2, 0x00, 0x00, last]);
+            assert!(mac.well_known().unwrap().is_link_local());
+        }
+
+        assert!(!MacAddress::MVRP.well_known().unwrap().is_link_local());
+        assert!(!MacAddress::BROADCAST.well_known().unwrap().is_link_local());
+    }
+}